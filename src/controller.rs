@@ -1,205 +1,3167 @@
-use std::{sync::Arc, time::Duration};
+use std::{
+    collections::{HashMap, HashSet},
+    hash::{BuildHasher, Hasher, RandomState},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
 
 use futures_util::StreamExt;
 use kube::{
-    Api, Client, Resource, ResourceExt,
-    api::{Patch, PatchParams},
+    Api, Client, CustomResourceExt, Resource, ResourceExt,
+    api::{DeleteParams, ListParams, Patch, PatchParams},
     runtime::{
-        controller::{Action, Controller},
+        controller::{self, Action, Controller},
+        events::{Event, EventType, Recorder},
+        finalizer::{self, Event as FinalizerEvent, finalizer},
+        reflector::{ObjectRef, Store},
+        wait::{await_condition, conditions},
         watcher::Config,
     },
 };
-use tracing::{error, info};
+use serde::{Serialize, de::DeserializeOwned};
+use tracing::{Instrument, debug, error, info, info_span, warn};
 
-use k8s_openapi::api::{
-    apps::v1::Deployment,
-    core::v1::{ConfigMap, Service},
-    networking::v1::Ingress,
+use k8s_openapi::{
+    api::{
+        apps::v1::Deployment,
+        autoscaling::v2::HorizontalPodAutoscaler,
+        core::v1::{ConfigMap, Pod, Secret, Service, ServiceAccount},
+        networking::v1::{Ingress, NetworkPolicy},
+        policy::v1::PodDisruptionBudget,
+    },
+    apiextensions_apiserver::pkg::apis::apiextensions::v1::CustomResourceDefinition,
+    chrono::{SecondsFormat, Utc},
 };
 
 use crate::{
-    crd::{HwCondition, RustOperator},
+    crd::{HwCondition, RustOperator, RustOperatorSpec, RustOperatorStatus, crd_without_formats},
+    leader_election::LeaderElector,
+    metrics::{self, Metrics},
     resources::{
-        FINALIZER, desired_configmap, desired_deployment, desired_ingress, desired_service, labels,
-        upsert_condition,
+        CHILD_SELECTOR, CONFIG_HASH_ANNOTATION, DesiredState, FINALIZER,
+        LAST_APPLIED_HASH_ANNOTATION, NAMESPACE_DEFAULTS_CONFIGMAP, apply_namespace_defaults,
+        build_desired_state, config_hash, desired_state_hash, ingress_hosts, is_owned_by,
+        label_selector, labels, pin_selector_expressions, pod_statuses, preserve_annotations,
+        reloads_by_sighup, sanitize_message, service_account_name, service_selects_pods, site_url,
+        tls_secret_ready, ttl_expiry, upsert_condition,
     },
+    webhook,
 };
 
+type InSync = Arc<Mutex<HashSet<ObjectRef<RustOperator>>>>;
+
 #[derive(Clone)]
 struct Ctx {
     client: Client,
+    recorder: Recorder,
+    metrics: Metrics,
+    /// Annotation key prefixes on children that a forced apply must never drop.
+    preserved_annotation_prefixes: Vec<String>,
+    /// Finalizer the operator adds; `None` with `DISABLE_FINALIZER`, leaving cleanup to owner refs.
+    finalizer: Option<String>,
+    /// Finalizer names to replace with `finalizer` (or just remove when it is disabled).
+    legacy_finalizers: Vec<String>,
+    /// `DRY_RUN=1`: send every apply/delete with dryRun and skip status, annotation, finalizer
+    /// and Event writes, logging what would change instead.
+    dry_run: bool,
+    /// Label keys every RustOperator must carry before its children are managed.
+    required_labels: Vec<String>,
+    /// Copy the required labels from the CR onto its children.
+    propagate_required_labels: bool,
+    /// Consecutive failed reconciles per object, driving the error backoff; cleared on success.
+    failures: Arc<Mutex<HashMap<ObjectRef<RustOperator>, u32>>>,
+    /// Objects whose last full pass completed with no child or pod event since; the watch
+    /// mappers drop an object as soon as one of its children changes.
+    in_sync: InSync,
+    /// Longest a single reconcile may run before it is abandoned and retried.
+    reconcile_timeout: Duration,
+}
+
+impl Ctx {
+    fn delete_params(&self) -> DeleteParams {
+        DeleteParams {
+            dry_run: self.dry_run,
+            ..Default::default()
+        }
+    }
+}
+
+/// True when `var` is set to `1` or `true`.
+fn env_flag(var: &str) -> bool {
+    std::env::var(var).is_ok_and(|v| matches!(v.trim(), "1" | "true"))
+}
+
+/// Annotations injected by admission controllers that the operator keeps on its children.
+/// Override with a comma-separated `PRESERVE_ANNOTATION_PREFIXES` (empty disables).
+const DEFAULT_PRESERVED_ANNOTATION_PREFIXES: &str =
+    "sidecar.istio.io/,vault.hashicorp.com/,linkerd.io/";
+
+/// Comma-separated list from `var`, or `default` when unset.
+fn env_list(var: &str, default: &str) -> Vec<String> {
+    std::env::var(var)
+        .unwrap_or_else(|_| default.into())
+        .split(',')
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+        .collect()
+}
+
+/// Finalizer names used by earlier releases, swapped for `FINALIZER` so those objects can still
+/// be deleted. Extend with a comma-separated `LEGACY_FINALIZERS`.
+const DEFAULT_LEGACY_FINALIZERS: &str = "";
+
+/// The active finalizer (`FINALIZER_NAME`, default `FINALIZER`; none with `DISABLE_FINALIZER`)
+/// and the names to migrate away from. `FINALIZER` itself counts as legacy once it is renamed or
+/// disabled, so objects created before the switch can still be deleted.
+fn finalizer_names() -> (Option<String>, Vec<String>) {
+    resolve_finalizers(
+        env_flag("DISABLE_FINALIZER"),
+        std::env::var("FINALIZER_NAME").ok(),
+        env_list("LEGACY_FINALIZERS", DEFAULT_LEGACY_FINALIZERS),
+    )
+}
+
+/// [`finalizer_names`] from settings already read from the environment.
+fn resolve_finalizers(
+    disabled: bool,
+    name: Option<String>,
+    mut legacy: Vec<String>,
+) -> (Option<String>, Vec<String>) {
+    let active = (!disabled).then(|| {
+        name.filter(|n| !n.trim().is_empty())
+            .unwrap_or_else(|| FINALIZER.into())
+    });
+    legacy.push(FINALIZER.into());
+    legacy.retain(|f| Some(f) != active.as_ref());
+    (active, legacy)
+}
+
+/// Why a reconcile was requeued; every returned `Action` goes through [`requeue`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum RequeueReason {
+    /// Children match the spec and pods are ready.
+    Steady,
+    /// Applied, but the Deployment has not reached its desired ready count.
+    Progressing,
+    /// Reconcile failed.
+    Error,
+    /// Reconcile failed on a write conflict.
+    Conflict,
+    /// The API server rejected a request with 429 Too Many Requests.
+    Throttled,
+    /// Nothing to do until the object changes (invalid spec, immutable field, deletion).
+    Paused,
+}
+
+impl RequeueReason {
+    fn as_str(self) -> &'static str {
+        match self {
+            RequeueReason::Steady => "steady",
+            RequeueReason::Progressing => "progressing",
+            RequeueReason::Error => "error",
+            RequeueReason::Conflict => "conflict",
+            RequeueReason::Throttled => "throttled",
+            RequeueReason::Paused => "paused",
+        }
+    }
+}
+
+/// Periodic resync when the spec does not set `requeue_seconds`.
+const DEFAULT_RESYNC: Duration = Duration::from_secs(30);
+
+fn requeue(ctx: &Ctx, reason: RequeueReason) -> Action {
+    requeue_every(ctx, reason, DEFAULT_RESYNC)
+}
+
+fn count_requeue(ctx: &Ctx, reason: RequeueReason) {
+    ctx.metrics
+        .requeues
+        .with_label_values(&[reason.as_str()])
+        .inc();
+}
+
+/// `Error` requeue after the `failures`-th consecutive failure of an object.
+fn requeue_after_error(ctx: &Ctx, failures: u32) -> Action {
+    count_requeue(ctx, RequeueReason::Error);
+    Action::requeue(error_backoff(failures))
+}
+
+/// Like `requeue`, but Steady/Progressing resync after `resync`.
+fn requeue_every(ctx: &Ctx, reason: RequeueReason, resync: Duration) -> Action {
+    count_requeue(ctx, reason);
+    match reason {
+        RequeueReason::Steady | RequeueReason::Progressing => Action::requeue(resync),
+        RequeueReason::Error => Action::requeue(error_backoff(1)),
+        RequeueReason::Conflict => Action::requeue(Duration::from_secs(10)),
+        // kube's ErrorResponse does not carry the Retry-After header, so use a short fixed
+        // delay instead of the generic error backoff.
+        RequeueReason::Throttled => Action::requeue(Duration::from_secs(3)),
+        RequeueReason::Paused => Action::await_change(),
+    }
+}
+
+/// First retry delay after a failed reconcile, doubled per consecutive failure up to
+/// `MAX_ERROR_BACKOFF` so a persistent failure (e.g. missing RBAC) doesn't hammer the API.
+const BASE_ERROR_BACKOFF: Duration = Duration::from_secs(10);
+const MAX_ERROR_BACKOFF: Duration = Duration::from_secs(300);
+
+/// Delay before retrying after the `failures`-th consecutive failure, with up to 20% jitter
+/// subtracted so objects failing together don't retry in lockstep.
+fn error_backoff(failures: u32) -> Duration {
+    let exp = BASE_ERROR_BACKOFF.saturating_mul(1 << failures.saturating_sub(1).min(16));
+    let delay = exp.min(MAX_ERROR_BACKOFF);
+    // RandomState is seeded differently on every call, which is all the randomness needed here.
+    let random = RandomState::new().build_hasher().finish();
+    delay.mul_f64(1.0 - (random % 1000) as f64 / 5000.0)
+}
+
+/// The CR's `requeue_seconds`; zero would hot-loop, so it falls back to the default like unset.
+/// Shortened so a pending `ttl_seconds_after_ready` expiry is acted on in time.
+fn resync_interval(obj: &RustOperator) -> Duration {
+    let resync = obj
+        .spec
+        .requeue_seconds
+        .filter(|s| *s > 0)
+        .map_or(DEFAULT_RESYNC, Duration::from_secs);
+    let conditions = obj.status.as_ref().and_then(|s| s.conditions.as_deref());
+    match ttl_expiry(&obj.spec, conditions.unwrap_or_default()) {
+        Some(at) => (at - Utc::now())
+            .to_std()
+            .map_or(resync, |left| resync.min(left + Duration::from_secs(1))),
+        None => resync,
+    }
+}
+
+/// Upper bound on reconciles running at once; further work waits in the controller's queue.
+/// Override with `RECONCILE_CONCURRENCY` (0 means unbounded).
+const DEFAULT_RECONCILE_CONCURRENCY: u16 = 8;
+
+/// Upper bound on one reconcile, so a hung API call can't pin a worker forever.
+/// Override with `RECONCILE_TIMEOUT_SECONDS` (0 falls back to the default).
+const DEFAULT_RECONCILE_TIMEOUT: Duration = Duration::from_secs(60);
+
+fn reconcile_timeout() -> Duration {
+    std::env::var("RECONCILE_TIMEOUT_SECONDS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|s| *s > 0)
+        .map_or(DEFAULT_RECONCILE_TIMEOUT, Duration::from_secs)
+}
+
+/// Watcher config for children and pods: only objects carrying the operator's name label are
+/// cached, rather than every Deployment, Service, etc. in the cluster. The RustOperator watch
+/// itself stays unfiltered, since CRs don't carry that label.
+fn child_watch_config() -> Config {
+    Config::default().labels(CHILD_SELECTOR)
+}
+
+fn controller_config() -> controller::Config {
+    let concurrency = std::env::var("RECONCILE_CONCURRENCY")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_RECONCILE_CONCURRENCY);
+    controller::Config::default()
+        .concurrency(concurrency)
+        .debounce(Duration::from_millis(500))
+}
+
+/// Checks that the RustOperator CRD is installed before watching it, since a missing CRD
+/// otherwise shows up as an endless watch error loop. With `AUTO_INSTALL_CRD=1` a missing CRD
+/// is applied from `RustOperator::crd()` instead.
+async fn ensure_crd_installed(client: &Client) -> anyhow::Result<()> {
+    let crds: Api<CustomResourceDefinition> = Api::all(client.clone());
+    let name = RustOperator::crd_name();
+    match crds.get_opt(name).await {
+        Ok(Some(_)) => return Ok(()),
+        Ok(None) => {}
+        // Without RBAC on CRDs the check can't run; let the watch report the real state.
+        Err(kube::Error::Api(resp)) if resp.code == 403 => {
+            warn!(
+                "cannot check that CRD {name} is installed: {}",
+                resp.message
+            );
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    }
+    anyhow::ensure!(
+        env_flag("AUTO_INSTALL_CRD"),
+        "CRD {name} is not installed; apply k8s/base/crd.yaml (or set AUTO_INSTALL_CRD=1) and restart the operator"
+    );
+    info!("CRD {name} is missing, installing it");
+    crds.patch(
+        name,
+        &PatchParams::apply("rust-operator"),
+        &Patch::Apply(crd_without_formats()?),
+    )
+    .await?;
+    let established = await_condition(crds, name, conditions::is_crd_established());
+    tokio::time::timeout(Duration::from_secs(30), established).await??;
+    Ok(())
 }
 
 pub async fn run_operator() -> anyhow::Result<()> {
     let client = Client::try_default().await?;
+    ensure_crd_installed(&client).await?;
     let root: Api<RustOperator> = Api::all(client.clone());
 
     let deploys: Api<Deployment> = Api::all(client.clone());
     let svcs: Api<Service> = Api::all(client.clone());
     let cms: Api<ConfigMap> = Api::all(client.clone());
     let ings: Api<Ingress> = Api::all(client.clone());
+    let hpas: Api<HorizontalPodAutoscaler> = Api::all(client.clone());
+    let pdbs: Api<PodDisruptionBudget> = Api::all(client.clone());
+    let netpols: Api<NetworkPolicy> = Api::all(client.clone());
+    let sas: Api<ServiceAccount> = Api::all(client.clone());
+    let pods: Api<Pod> = Api::all(client.clone());
+
+    let (finalizer, legacy_finalizers) = finalizer_names();
+    let metrics = Metrics::new();
+    let ready = Arc::new(AtomicBool::new(false));
+    let metrics_server = metrics::metrics_addr()?.map(|addr| {
+        let metrics = metrics.clone();
+        let ready = ready.clone();
+        tokio::spawn(async move {
+            if let Err(e) = metrics::serve(addr, metrics, ready).await {
+                error!("metrics server failed: {e:?}");
+            }
+        })
+    });
+    let webhook_server = webhook::webhook_addr()?.map(|addr| {
+        tokio::spawn(async move {
+            if let Err(e) = webhook::serve(addr).await {
+                error!("webhook server failed: {e:?}");
+            }
+        })
+    });
+
+    // With ENABLE_LEADER_ELECTION only the Lease holder runs the controller; standbys wait here
+    // and report ready, since readiness otherwise tracks a cache they don't have yet.
+    let elector =
+        env_flag("ENABLE_LEADER_ELECTION").then(|| Arc::new(LeaderElector::new(client.clone())));
+    if let Some(elector) = &elector {
+        ready.store(true, Ordering::Relaxed);
+        tokio::select! {
+            _ = elector.acquire() => ready.store(false, Ordering::Relaxed),
+            _ = shutdown_signal() => {
+                for server in [metrics_server, webhook_server].into_iter().flatten() {
+                    server.abort();
+                }
+                return Ok(());
+            }
+        }
+    }
+    // Losing the Lease stops the controller like a shutdown signal would.
+    let lost_lease = Arc::new(AtomicBool::new(false));
+    let renewal = elector.clone().map(|elector| {
+        let lost_lease = lost_lease.clone();
+        tokio::spawn(async move {
+            elector.hold().await;
+            lost_lease.store(true, Ordering::Relaxed);
+        })
+    });
+    let renew_abort = renewal.as_ref().map(|r| r.abort_handle());
+    let stop = async move {
+        match renewal {
+            Some(renewal) => {
+                tokio::select! {
+                    _ = shutdown_signal() => {}
+                    _ = renewal => {}
+                }
+            }
+            None => shutdown_signal().await,
+        }
+    };
+
+    let controller = Controller::new(root, Config::default());
+    let cache_sync = tokio::spawn(report_ready_on_sync(controller.store(), ready));
 
-    Controller::new(root, Config::default())
-        .owns(deploys, Config::default())
-        .owns(svcs, Config::default())
-        .owns(cms, Config::default())
-        .owns(ings, Config::default())
-        .run(reconcile, error_policy, Arc::new(Ctx { client }))
+    // Like `owns`, but every child event also takes the owner off the fast path.
+    let in_sync = InSync::default();
+    controller
+        .watches(
+            deploys,
+            child_watch_config(),
+            mark_changed(&in_sync, owners_of),
+        )
+        .watches(
+            svcs,
+            child_watch_config(),
+            mark_changed(&in_sync, owners_of),
+        )
+        .watches(cms, child_watch_config(), mark_changed(&in_sync, owners_of))
+        .watches(
+            ings,
+            child_watch_config(),
+            mark_changed(&in_sync, owners_of),
+        )
+        .watches(
+            hpas,
+            child_watch_config(),
+            mark_changed(&in_sync, owners_of),
+        )
+        .watches(
+            pdbs,
+            child_watch_config(),
+            mark_changed(&in_sync, owners_of),
+        )
+        .watches(
+            netpols,
+            child_watch_config(),
+            mark_changed(&in_sync, owners_of),
+        )
+        .watches(sas, child_watch_config(), mark_changed(&in_sync, owners_of))
+        .watches(
+            pods,
+            child_watch_config(),
+            mark_changed(&in_sync, pod_to_owner),
+        )
+        .with_config(controller_config())
+        .graceful_shutdown_on(stop)
+        .run(
+            reconcile,
+            error_policy,
+            Arc::new(Ctx {
+                recorder: Recorder::new(client.clone(), "rust-operator".into()),
+                client,
+                metrics,
+                preserved_annotation_prefixes: env_list(
+                    "PRESERVE_ANNOTATION_PREFIXES",
+                    DEFAULT_PRESERVED_ANNOTATION_PREFIXES,
+                ),
+                finalizer,
+                legacy_finalizers,
+                dry_run: env_flag("DRY_RUN"),
+                required_labels: env_list("REQUIRED_LABELS", ""),
+                propagate_required_labels: env_flag("PROPAGATE_REQUIRED_LABELS"),
+                failures: Arc::default(),
+                in_sync,
+                reconcile_timeout: reconcile_timeout(),
+            }),
+        )
         .for_each(|res| async move {
             match res {
-                Ok((objref, _action)) => info!("✅ reconciled {}", objref.name),
-                Err(e) => error!("❌ reconcile failed: {e:?}"),
+                Ok((objref, _action)) => info!(
+                    name = %objref.name,
+                    ns = objref.namespace.as_deref().unwrap_or_default(),
+                    outcome = "reconciled",
+                    "✅ reconciled {}",
+                    objref.name
+                ),
+                Err(controller::Error::ReconcilerFailed(e, objref)) => error!(
+                    name = %objref.name,
+                    ns = objref.namespace.as_deref().unwrap_or_default(),
+                    outcome = "failed",
+                    "❌ reconcile failed: {e:?}"
+                ),
+                Err(e) => error!(outcome = "failed", "❌ reconcile failed: {e:?}"),
             }
         })
         .await;
+    info!("controller stopped");
+
+    cache_sync.abort();
+    for server in [metrics_server, webhook_server].into_iter().flatten() {
+        server.abort();
+    }
+    if let Some(renew_abort) = renew_abort {
+        renew_abort.abort();
+    }
+    if lost_lease.load(Ordering::Relaxed) {
+        // Exit so the pod restarts as a standby rather than reconciling alongside the new leader.
+        anyhow::bail!("lost leader lease");
+    }
+    if let Some(elector) = elector {
+        elector.release().await;
+    }
+    Ok(())
+}
+
+/// How often `report_ready_on_sync` re-checks whether the cache has synced.
+const CACHE_SYNC_POLL: Duration = Duration::from_secs(1);
+
+/// Sets `ready` only once the RustOperator cache holds the initial list, so a fresh replica
+/// doesn't look healthy while it still can't see any objects. The controller's own runner waits
+/// on the same one-shot signal and can take its only wakeup, so the wait is re-armed every
+/// `CACHE_SYNC_POLL` instead of relying on being woken.
+async fn report_ready_on_sync(store: Store<RustOperator>, ready: Arc<AtomicBool>) {
+    loop {
+        match tokio::time::timeout(CACHE_SYNC_POLL, store.wait_until_ready()).await {
+            Ok(Ok(())) => {
+                info!("controller cache synced");
+                ready.store(true, Ordering::Relaxed);
+                return;
+            }
+            Ok(Err(_)) => return,
+            Err(_) => {}
+        }
+    }
+}
+
+/// Resolves on SIGTERM (sent by the kubelet when the operator pod is replaced) or Ctrl+C. The
+/// controller then stops picking up new work and waits for in-flight reconciles to finish.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        let _ = tokio::signal::ctrl_c().await;
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut sig) => {
+                sig.recv().await;
+            }
+            Err(e) => {
+                error!("cannot listen for SIGTERM: {e}");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+    info!("shutdown requested, waiting for in-flight reconciles to finish");
+}
+
+/// The RustOperators listed in a child's owner references.
+fn owners_of<K: Resource>(child: K) -> Vec<ObjectRef<RustOperator>> {
+    let ns = child.namespace();
+    child
+        .owner_references()
+        .iter()
+        .filter_map(|owner| ObjectRef::from_owner_ref(ns.as_deref(), owner, ()))
+        .collect()
+}
+
+/// Wraps a watch mapper so the objects it triggers are dropped from `in_sync` first, making the
+/// reconcile the event causes a full pass.
+fn mark_changed<K, I, M>(
+    in_sync: &InSync,
+    mapper: M,
+) -> impl Fn(K) -> Vec<ObjectRef<RustOperator>> + use<K, I, M>
+where
+    M: Fn(K) -> I,
+    I: IntoIterator<Item = ObjectRef<RustOperator>>,
+{
+    let in_sync = in_sync.clone();
+    move |obj| {
+        let owners: Vec<_> = mapper(obj).into_iter().collect();
+        let mut in_sync = in_sync.lock().expect("in_sync lock");
+        for owner in &owners {
+            in_sync.remove(owner);
+        }
+        owners
+    }
+}
+
+/// Pods are owned by ReplicaSets, so map them back to the CR via the instance label.
+fn pod_to_owner(pod: Pod) -> Option<ObjectRef<RustOperator>> {
+    let ns = pod.namespace()?;
+    let instance = pod.labels().get("app.kubernetes.io/instance")?;
+    Some(ObjectRef::new(instance).within(&ns))
+}
 
+/// Per-CR override of log verbosity for its own reconciles (e.g. "debug").
+const LOG_LEVEL_ANNOTATION: &str = "rootster.xyz/log-level";
+
+async fn reconcile(
+    obj: Arc<RustOperator>,
+    ctx: Arc<Ctx>,
+) -> Result<Action, finalizer::Error<kube::Error>> {
+    let log_level = obj
+        .annotations()
+        .get(LOG_LEVEL_ANNOTATION)
+        .map(|l| l.trim().to_lowercase())
+        .unwrap_or_default();
+    // Not `name`: the JSON log format already uses that key for the span's own name.
+    let span = info_span!(
+        "reconcile",
+        object = %obj.name_any(),
+        ns = %obj.namespace().unwrap_or_default(),
+        generation = obj.meta().generation,
+        log_level = %log_level,
+    );
+    ctx.metrics.reconciles.inc();
+    let _timer = ctx.metrics.reconcile_duration.start_timer();
+    let _in_flight = ctx.metrics.track_in_flight();
+    let objref = ObjectRef::from_obj(&*obj);
+    let timeout = ctx.reconcile_timeout;
+    let result = match tokio::time::timeout(timeout, reconcile_inner(obj, ctx.clone()))
+        .instrument(span.clone())
+        .await
+    {
+        Ok(result) => result,
+        Err(_) => {
+            span.in_scope(|| warn!("reconcile timed out after {timeout:?}"));
+            // Surface as an apply failure so error_policy requeues it with the usual backoff.
+            Err(finalizer::Error::ApplyFailed(kube::Error::Service(
+                format!("reconcile timed out after {timeout:?}").into(),
+            )))
+        }
+    };
+    if result.is_ok() {
+        ctx.failures.lock().expect("failures lock").remove(&objref);
+    }
+    result
+}
+
+/// Adds or removes only our finalizer (leaving any others alone) and dispatches to apply/cleanup.
+async fn reconcile_inner(
+    obj: Arc<RustOperator>,
+    ctx: Arc<Ctx>,
+) -> Result<Action, finalizer::Error<kube::Error>> {
+    let ns = obj.namespace().unwrap_or_else(|| "default".into());
+    debug!("reconciling generation {:?}", obj.meta().generation);
+    if ctx.dry_run {
+        // Adding or removing the finalizer would mutate the CR, so skip the helper entirely.
+        return if obj.meta().deletion_timestamp.is_some() {
+            cleanup_children(&obj, &ns, &ctx)
+                .await
+                .map(|_| requeue(&ctx, RequeueReason::Paused))
+                .map_err(finalizer::Error::CleanupFailed)
+        } else {
+            apply(obj, ctx.clone())
+                .await
+                .map_err(finalizer::Error::ApplyFailed)
+        };
+    }
+    let api: Api<RustOperator> = Api::namespaced(ctx.client.clone(), &ns);
+    if migrate_finalizers(&api, &obj, &ctx.legacy_finalizers, ctx.finalizer.as_deref())
+        .await
+        .map_err(finalizer::Error::AddFinalizer)?
+    {
+        // The patch triggers a fresh reconcile that sees the current finalizer.
+        return Ok(requeue(&ctx, RequeueReason::Paused));
+    }
+    let Some(name) = ctx.finalizer.as_deref() else {
+        // Without a finalizer the owner references let the garbage collector remove the children.
+        return if obj.meta().deletion_timestamp.is_some() {
+            Ok(requeue(&ctx, RequeueReason::Paused))
+        } else {
+            apply(obj, ctx.clone())
+                .await
+                .map_err(finalizer::Error::ApplyFailed)
+        };
+    };
+    finalizer(&api, name, obj, |event| async {
+        match event {
+            FinalizerEvent::Apply(obj) => apply(obj, ctx.clone()).await,
+            FinalizerEvent::Cleanup(obj) => {
+                cleanup_children(&obj, &ns, &ctx).await?;
+                forget(&ctx, &obj);
+                Ok(requeue(&ctx, RequeueReason::Paused))
+            }
+        }
+    })
+    .await
+}
+
+/// Drops the per-object bookkeeping in `ctx` for a CR whose cleanup is done, so deleted objects
+/// don't pile up in it.
+fn forget(ctx: &Ctx, obj: &RustOperator) {
+    let objref = ObjectRef::from_obj(obj);
+    ctx.failures.lock().expect("failures lock").remove(&objref);
+    ctx.in_sync.lock().expect("in_sync lock").remove(&objref);
+}
+
+/// Replaces any `legacy` finalizer on `obj` with `active`, keeping other controllers'
+/// finalizers. Nothing is added when `active` is `None` or the object is already being deleted
+/// (the API rejects new finalizers then). The resourceVersion precondition makes a concurrent
+/// edit fail with a conflict instead of being overwritten. Returns whether a patch was sent.
+async fn migrate_finalizers(
+    api: &Api<RustOperator>,
+    obj: &RustOperator,
+    legacy: &[String],
+    active: Option<&str>,
+) -> Result<bool, kube::Error> {
+    let current = obj.finalizers();
+    if !current.iter().any(|f| legacy.contains(f)) {
+        return Ok(false);
+    }
+    let mut migrated: Vec<&str> = current
+        .iter()
+        .filter(|f| !legacy.contains(f))
+        .map(String::as_str)
+        .collect();
+    if let Some(active) = active
+        && obj.meta().deletion_timestamp.is_none()
+        && !migrated.contains(&active)
+    {
+        migrated.push(active);
+    }
+    info!(
+        "migrating finalizers of {} from {current:?} to {migrated:?}",
+        obj.name_any()
+    );
+    let patch = serde_json::json!({
+        "metadata": { "finalizers": migrated, "resourceVersion": obj.resource_version() }
+    });
+    api.patch(
+        &obj.name_any(),
+        &PatchParams::default(),
+        &Patch::Merge(&patch),
+    )
+    .await?;
+    Ok(true)
+}
+
+/// Sets `CONFIG_HASH_ANNOTATION` on every pod that hasn't seen `hash` yet. The downward API
+/// volume mounted into the sidecars picks the change up, which they turn into an nginx reload.
+async fn signal_reload(api: &Api<Pod>, pods: &[Pod], hash: &str) -> Result<(), kube::Error> {
+    for pod in pods {
+        if pod
+            .annotations()
+            .get(CONFIG_HASH_ANNOTATION)
+            .map(String::as_str)
+            == Some(hash)
+        {
+            continue;
+        }
+        debug!("signalling reload to pod {}", pod.name_any());
+        let patch = serde_json::json!({
+            "metadata": { "annotations": { CONFIG_HASH_ANNOTATION: hash } }
+        });
+        api.patch(
+            &pod.name_any(),
+            &PatchParams::default(),
+            &Patch::Merge(&patch),
+        )
+        .await?;
+    }
     Ok(())
 }
 
-async fn reconcile(obj: Arc<RustOperator>, ctx: Arc<Ctx>) -> Result<Action, kube::Error> {
+/// Brings the children and status in line with the spec.
+async fn apply(obj: Arc<RustOperator>, ctx: Arc<Ctx>) -> Result<Action, kube::Error> {
     let ns = obj.namespace().unwrap_or_else(|| "default".into());
     let name = obj.name_any();
 
-    if obj.meta().deletion_timestamp.is_some() {
-        cleanup_children(&name, &ns, &ctx).await?;
-        ensure_finalizer(&name, &ns, &ctx, false).await?;
-        return Ok(Action::await_change());
+    // The spec can't change without bumping the generation, so a mismatch at the same generation
+    // means the status was written by something other than this operator.
+    let spec_hash = desired_state_hash(&obj.spec);
+    if let Some(status) = &obj.status
+        && status.observed_generation == obj.meta().generation
+        && status
+            .applied_spec_hash
+            .as_ref()
+            .is_some_and(|h| *h != spec_hash)
+    {
+        warn!(
+            "{ns}/{name}: status.applied_spec_hash does not match the spec at generation {:?}",
+            obj.meta().generation
+        );
+    }
+
+    let conditions = obj.status.as_ref().and_then(|s| s.conditions.as_deref());
+    if let Some(at) = ttl_expiry(&obj.spec, conditions.unwrap_or_default())
+        && at <= Utc::now()
+    {
+        info!("{ns}/{name} expired at {at} (ttl_seconds_after_ready), deleting it");
+        publish_event(
+            &ctx,
+            &obj,
+            EventType::Normal,
+            "Expired",
+            "Delete",
+            format!("Ready for ttl_seconds_after_ready, deleting at {at}"),
+        )
+        .await;
+        let api: Api<RustOperator> = Api::namespaced(ctx.client.clone(), &ns);
+        api.delete(&name, &ctx.delete_params()).await?;
+        return Ok(requeue(&ctx, RequeueReason::Paused));
+    }
+
+    // Owner refs need the CR's UID; an object without one can't own children yet.
+    let Some(mut owner) = obj.controller_owner_ref(&()) else {
+        warn!("{ns}/{name} has no uid, skipping until it changes");
+        return Ok(requeue(&ctx, RequeueReason::Paused));
+    };
+    if !obj.spec.controller_owner {
+        owner.controller = None;
+    }
+    // Namespace defaults only fill what the CR leaves unset, so render from a merged copy.
+    let mut spec = obj.spec.clone();
+    if let Some(defaults) = Api::<ConfigMap>::namespaced(ctx.client.clone(), &ns)
+        .get_opt(NAMESPACE_DEFAULTS_CONFIGMAP)
+        .await?
+        .and_then(|cm| cm.data)
+    {
+        apply_namespace_defaults(&mut spec, &defaults);
+    }
+    let missing: Vec<&str> = ctx
+        .required_labels
+        .iter()
+        .filter(|k| !obj.labels().contains_key(*k))
+        .map(String::as_str)
+        .collect();
+    if !missing.is_empty() {
+        let msg = format!("missing required labels: {}", missing.join(", "));
+        warn!("not managing {ns}/{name}: {msg}");
+        report_blocked(&obj, &ctx, &ns, policy_condition(Some(msg))).await?;
+        return Ok(requeue(&ctx, RequeueReason::Paused));
+    }
+    if ctx.propagate_required_labels {
+        for key in &ctx.required_labels {
+            spec.extra_labels
+                .entry(key.clone())
+                .or_insert_with(|| obj.labels()[key].clone());
+        }
+    }
+    let mut desired = match build_desired_state(&spec, &name, obj.meta().generation, owner) {
+        Ok(desired) => desired,
+        Err(msg) => {
+            warn!("invalid spec for {ns}/{name}: {msg}");
+            report_blocked(&obj, &ctx, &ns, degraded_condition(Some(msg))).await?;
+            return Ok(requeue(&ctx, RequeueReason::Paused));
+        }
+    };
+
+    let desired_hash = desired_state_hash(&spec);
+    let objref = ObjectRef::from_obj(&*obj);
+    if obj.annotations().get(LAST_APPLIED_HASH_ANNOTATION) == Some(&desired_hash)
+        && status_in_sync(&obj, &desired, &ctx)
+    {
+        debug!("desired state unchanged since last apply, skipping");
+        return Ok(requeue_every(
+            &ctx,
+            RequeueReason::Steady,
+            resync_interval(&obj),
+        ));
     }
 
-    ensure_finalizer(&name, &ns, &ctx, true).await?;
+    let spec_changed = obj.annotations().get(LAST_APPLIED_HASH_ANNOTATION) != Some(&desired_hash);
+    // Marked before any apply, so an event for a child that changes during this pass (our own
+    // applies included) takes it off again and the next reconcile is a full one.
+    ctx.in_sync
+        .lock()
+        .expect("in_sync lock")
+        .insert(objref.clone());
 
     let labels = labels(&name);
-    let owner = obj.controller_owner_ref(&()).expect("owner ref");
 
+    debug!("applying children for {ns}/{name}");
+    // A failed child apply doesn't stop the others; the failures are reported on the
+    // PartialFailure condition and the first one is returned once status is written.
+    let mut failures: Vec<(&str, kube::Error)> = Vec::new();
     let cm_api: Api<ConfigMap> = Api::namespaced(ctx.client.clone(), &ns);
-    let cm = desired_configmap(&name, &labels, &obj.spec.html, owner.clone());
-    cm_api
-        .patch(
-            &name,
-            &PatchParams::apply("rust-operator").force(),
-            &Patch::Apply(&cm),
-        )
-        .await?;
+    match apply_child(&cm_api, &name, &desired.configmap, &obj, &ctx, &ns).await {
+        Ok(Some(_)) if spec_changed => {
+            publish_event(
+                &ctx,
+                &obj,
+                EventType::Normal,
+                "ConfigMapApplied",
+                "Apply",
+                format!("applied ConfigMap {name}"),
+            )
+            .await;
+        }
+        Ok(Some(_)) => {}
+        Ok(None) => return Ok(requeue(&ctx, RequeueReason::Paused)),
+        Err(e) => failures.push(("ConfigMap", e)),
+    }
 
+    let config_only = desired.deployment.is_none();
     let deploy_api: Api<Deployment> = Api::namespaced(ctx.client.clone(), &ns);
-    let deploy = desired_deployment(&name, &labels, obj.spec.replicas, owner.clone(), &obj.spec);
-    let deploy_obj = deploy_api
-        .patch(
-            &name,
-            &PatchParams::apply("rust-operator").force(),
-            &Patch::Apply(&deploy),
-        )
-        .await?;
+    let mut selector_edit_ignored = false;
+    let deploy_obj = match &mut desired.deployment {
+        // Config-only users bring their own Deployment, so never delete one by name here; a
+        // Deployment this CR created earlier is removed by `prune_owned` below.
+        None => None,
+        Some(deploy) => {
+            if let Some(live) = deploy_api.get_opt(&name).await? {
+                selector_edit_ignored = pin_selector_expressions(deploy, &live);
+            }
+            match apply_child(&deploy_api, &name, deploy, &obj, &ctx, &ns).await {
+                Ok(Some(d)) => Some(d),
+                Ok(None) => return Ok(requeue(&ctx, RequeueReason::Paused)),
+                Err(e) => {
+                    failures.push(("Deployment", e));
+                    None
+                }
+            }
+        }
+    };
+    if spec_changed && let Some(d) = &deploy_obj {
+        let current = d.status.as_ref().and_then(|s| s.replicas).unwrap_or(0);
+        if desired.hpa.is_none() && current != obj.spec.replicas {
+            publish_event(
+                &ctx,
+                &obj,
+                EventType::Normal,
+                "DeploymentScaled",
+                "Scale",
+                format!(
+                    "scaling Deployment {name} from {current} to {} replicas",
+                    obj.spec.replicas
+                ),
+            )
+            .await;
+        }
+    }
+
+    let hpa_api: Api<HorizontalPodAutoscaler> = Api::namespaced(ctx.client.clone(), &ns);
+    if let Some(hpa) = &desired.hpa {
+        match apply_child(&hpa_api, &name, hpa, &obj, &ctx, &ns).await {
+            Ok(Some(_)) => {}
+            Ok(None) => return Ok(requeue(&ctx, RequeueReason::Paused)),
+            Err(e) => failures.push(("HorizontalPodAutoscaler", e)),
+        }
+    }
+    // Without autoscaling, `prune_owned` below removes an HPA this CR created; a same-named one
+    // someone else created is left alone.
+
+    let sa_api: Api<ServiceAccount> = Api::namespaced(ctx.client.clone(), &ns);
+    if let Some(sa) = &desired.service_account {
+        // Applying over an existing account (e.g. `default`) would adopt it, and this CR's
+        // deletion would then take it down, so only ever manage one this CR created.
+        let uid = obj.uid().unwrap_or_default();
+        if sa_api
+            .get_opt(&sa.name_any())
+            .await?
+            .is_some_and(|live| !is_owned_by(live.meta(), &uid))
+        {
+            let msg = format!(
+                "ServiceAccount {} already exists and is not owned by this RustOperator; \
+                 set create_service_account to false to use it",
+                sa.name_any()
+            );
+            warn!("not adopting for {ns}/{name}: {msg}");
+            let condition = HwCondition {
+                type_: "Degraded".into(),
+                status: "True".into(),
+                reason: Some("ServiceAccountExists".into()),
+                message: Some(msg),
+                last_transition_time: None,
+            };
+            report_blocked(&obj, &ctx, &ns, condition).await?;
+            return Ok(requeue(&ctx, RequeueReason::Paused));
+        }
+        match apply_child(&sa_api, &sa.name_any(), sa, &obj, &ctx, &ns).await {
+            Ok(Some(_)) => {}
+            Ok(None) => return Ok(requeue(&ctx, RequeueReason::Paused)),
+            Err(e) => failures.push(("ServiceAccount", e)),
+        }
+    }
+
+    let pdb_api: Api<PodDisruptionBudget> = Api::namespaced(ctx.client.clone(), &ns);
+    if let Some(pdb) = &desired.pdb {
+        match apply_child(&pdb_api, &name, pdb, &obj, &ctx, &ns).await {
+            Ok(Some(_)) => {}
+            Ok(None) => return Ok(requeue(&ctx, RequeueReason::Paused)),
+            Err(e) => failures.push(("PodDisruptionBudget", e)),
+        }
+    }
+
+    let netpol_api: Api<NetworkPolicy> = Api::namespaced(ctx.client.clone(), &ns);
+    if let Some(netpol) = &desired.network_policy {
+        match apply_child(&netpol_api, &name, netpol, &obj, &ctx, &ns).await {
+            Ok(Some(_)) => {}
+            Ok(None) => return Ok(requeue(&ctx, RequeueReason::Paused)),
+            Err(e) => failures.push(("NetworkPolicy", e)),
+        }
+    }
 
     let svc_api: Api<Service> = Api::namespaced(ctx.client.clone(), &ns);
-    let svc_name = format!("{name}-service");
-    let svc = desired_service(&name, &labels, &obj.spec.service_type, owner.clone());
-    svc_api
-        .patch(
-            &svc_name,
-            &PatchParams::apply("rust-operator").force(),
-            &Patch::Apply(&svc),
-        )
-        .await?;
+    let managed_svc_name = format!("{name}-service");
+    let selector_ok = if let Some(svc) = &desired.service {
+        match apply_child(&svc_api, &managed_svc_name, svc, &obj, &ctx, &ns).await {
+            Ok(Some(_)) => {}
+            Ok(None) => return Ok(requeue(&ctx, RequeueReason::Paused)),
+            Err(e) => failures.push(("Service", e)),
+        }
+
+        // Checked against the Deployment as the API server returned it, since the desired one is
+        // built from the same labels and always matches. In config-only mode (or when the
+        // Deployment apply failed) there is nothing of ours to compare.
+        let selector_ok = deploy_obj
+            .as_ref()
+            .is_none_or(|deploy| service_selects_pods(svc, deploy));
+        if !selector_ok {
+            warn!(
+                "service {managed_svc_name} selector does not match pod labels of deployment {name}"
+            );
+        }
+        selector_ok
+    } else {
+        true
+    };
 
+    let had_ingress = obj
+        .status
+        .as_ref()
+        .and_then(|s| s.managed_resources.as_ref())
+        .is_some_and(|refs| refs.iter().any(|r| r.kind == "Ingress"));
     let ing_api: Api<Ingress> = Api::namespaced(ctx.client.clone(), &ns);
-    if !obj.spec.ingress_host.trim().is_empty() {
-        let ing = desired_ingress(
-            &name,
-            &labels,
-            &svc_name,
-            &obj.spec.ingress_host,
-            &obj.spec.tls_secret_name,
-            owner.clone(),
-        );
-        ing_api
-            .patch(
-                &name,
-                &PatchParams::apply("rust-operator").force(),
-                &Patch::Apply(&ing),
+    if let Some(ing) = &desired.ingress {
+        let applied = match apply_child(&ing_api, &name, ing, &obj, &ctx, &ns).await {
+            Ok(Some(_)) => true,
+            Ok(None) => return Ok(requeue(&ctx, RequeueReason::Paused)),
+            Err(e) => {
+                failures.push(("Ingress", e));
+                false
+            }
+        };
+        if applied && !had_ingress {
+            publish_event(
+                &ctx,
+                &obj,
+                EventType::Normal,
+                "IngressCreated",
+                "Create",
+                format!(
+                    "created Ingress {name} for {}",
+                    ingress_hosts(&spec).join(", ")
+                ),
             )
-            .await?;
-    } else {
-        let _ = ing_api.delete(&name, &Default::default()).await.ok();
+            .await;
+        }
+    } else if ing_api.get_opt(&name).await?.is_some() {
+        // Most CRs never have an Ingress, so look before deleting rather than 404 every pass;
+        // a delete racing another one is still ignored.
+        let _ = ing_api.delete(&name, &ctx.delete_params()).await.ok();
+        if had_ingress {
+            publish_event(
+                &ctx,
+                &obj,
+                EventType::Normal,
+                "IngressDeleted",
+                "Delete",
+                format!("deleted Ingress {name}"),
+            )
+            .await;
+        }
+    }
+
+    if let Some(uid) = obj.uid() {
+        let selector = label_selector(&labels);
+        let ingress_name = desired.ingress.is_some().then(|| name.clone());
+        let svc_keep = desired.service.is_some().then(|| managed_svc_name.clone());
+        let deploy_keep = (!config_only).then(|| name.clone());
+        let hpa_keep = desired.hpa.is_some().then(|| name.clone());
+        let pdb_keep = desired.pdb.is_some().then(|| name.clone());
+        let netpol_keep = desired.network_policy.is_some().then(|| name.clone());
+        let sa_keep = desired.service_account.as_ref().map(|sa| sa.name_any());
+        let dp = ctx.delete_params();
+        prune_owned(&cm_api, &selector, &uid, Some(name.clone()), &dp).await?;
+        prune_owned(&deploy_api, &selector, &uid, deploy_keep, &dp).await?;
+        prune_owned(&hpa_api, &selector, &uid, hpa_keep, &dp).await?;
+        prune_owned(&pdb_api, &selector, &uid, pdb_keep, &dp).await?;
+        prune_owned(&netpol_api, &selector, &uid, netpol_keep, &dp).await?;
+        prune_owned(&sa_api, &selector, &uid, sa_keep, &dp).await?;
+        prune_owned(&svc_api, &selector, &uid, svc_keep, &dp).await?;
+        prune_owned(&ing_api, &selector, &uid, ingress_name, &dp).await?;
     }
 
     let ready = deploy_obj
-        .status
         .as_ref()
+        .and_then(|d| d.status.as_ref())
         .and_then(|s| s.ready_replicas)
         .unwrap_or(0);
 
-    let ready_condition = HwCondition {
-        type_: "Ready".into(),
-        status: if ready > 0 {
-            "True".into()
-        } else {
-            "False".into()
-        },
-        reason: Some(if ready > 0 {
-            "PodsAvailable".into()
-        } else {
-            "Scaling".into()
-        }),
-        message: Some(format!("ready_replicas={ready}")),
+    let pod_api: Api<Pod> = Api::namespaced(ctx.client.clone(), &ns);
+    let pods = pod_api
+        .list(&ListParams::default().labels(&label_selector(&labels)))
+        .await?;
+    if reloads_by_sighup(&spec) && !config_only && !ctx.dry_run {
+        signal_reload(&pod_api, &pods.items, &config_hash(&spec)).await?;
+    }
+    let pods = pod_statuses(&pods.items);
+
+    // The live Deployment's count, so an HPA's current scale is what has to be ready.
+    let desired_replicas = deploy_obj
+        .as_ref()
+        .and_then(|d| d.spec.as_ref())
+        .and_then(|s| s.replicas)
+        .unwrap_or(1);
+    let pods_ready = if obj.spec.require_all_replicas {
+        ready >= desired_replicas
+    } else {
+        ready > 0
+    };
+    // `replicas: 0` without an HPA is a deliberate pause, not a rollout that hasn't caught up.
+    let scaled_to_zero = !config_only && spec.autoscaling.is_none() && spec.replicas == 0;
+    let ready_condition = if config_only {
+        HwCondition {
+            type_: "Ready".into(),
+            status: "True".into(),
+            reason: Some("ConfigOnly".into()),
+            message: Some("deployment is not managed in config-only mode".into()),
+            last_transition_time: None,
+        }
+    } else {
+        HwCondition {
+            type_: "Ready".into(),
+            status: if pods_ready && !scaled_to_zero {
+                "True".into()
+            } else {
+                "False".into()
+            },
+            reason: Some(if scaled_to_zero {
+                "ScaledToZero".into()
+            } else if pods_ready {
+                "PodsAvailable".into()
+            } else {
+                "Scaling".into()
+            }),
+            message: Some(format!("ready_replicas={ready}/{desired_replicas}")),
+            last_transition_time: None,
+        }
     };
 
+    // Mirrors the Deployment's own Available/Progressing semantics; absent in config-only mode.
+    let rollout_conditions = deploy_obj.as_ref().map(rollout_conditions);
+
     let mut new_status = obj.status.clone().unwrap_or_default();
 
-    if new_status.observed_message.as_deref() != Some(&obj.spec.message) {
-        new_status.observed_message = Some(obj.spec.message.clone());
+    let message = sanitize_message(&obj.spec.message);
+    if new_status.observed_message.as_deref() != Some(&message) {
+        debug!("observed message for {ns}/{name}: {message}");
+        new_status.observed_message = Some(message);
     }
     if new_status.ready_replicas != Some(ready) {
         new_status.ready_replicas = Some(ready);
     }
+    if new_status.pod_statuses.as_ref() != Some(&pods) {
+        new_status.pod_statuses = Some(pods);
+    }
+    new_status.url = site_url(&spec, &name, &ns);
+    let managed = desired.managed_refs();
+    if new_status.managed_resources.as_ref() != Some(&managed) {
+        new_status.managed_resources = Some(managed);
+    }
+    // Every child for this generation was applied above, unless some failed.
+    new_status.up_to_date = Some(failures.is_empty());
+    // Both describe the spec last fully reconciled, so a partial apply leaves them behind.
+    if failures.is_empty() {
+        new_status.observed_generation = obj.meta().generation;
+        new_status.applied_spec_hash = Some(spec_hash);
+    }
 
     let mut conditions = new_status.conditions.take().unwrap_or_default();
     upsert_condition(&mut conditions, ready_condition);
+    match rollout_conditions {
+        Some(rollout) => rollout
+            .into_iter()
+            .for_each(|c| upsert_condition(&mut conditions, c)),
+        None => conditions.retain(|c| !matches!(c.type_.as_str(), "Available" | "Progressing")),
+    }
+    if config_only {
+        conditions.retain(|c| c.type_ != "Paused");
+    } else {
+        upsert_condition(&mut conditions, paused_condition(scaled_to_zero));
+    }
+    upsert_condition(&mut conditions, degraded_condition(None));
+    if ctx.required_labels.is_empty() {
+        conditions.retain(|c| c.type_ != "PolicyViolation");
+    } else {
+        upsert_condition(&mut conditions, policy_condition(None));
+    }
+    upsert_condition(
+        &mut conditions,
+        HwCondition {
+            type_: "ImmutableFieldChanged".into(),
+            status: "False".into(),
+            reason: Some("Applied".into()),
+            message: None,
+            last_transition_time: None,
+        },
+    );
+    upsert_condition(&mut conditions, selector_condition(selector_ok));
+    upsert_condition(
+        &mut conditions,
+        HwCondition {
+            type_: "SelectorExpressionsChanged".into(),
+            status: if selector_edit_ignored {
+                "True".into()
+            } else {
+                "False".into()
+            },
+            reason: Some(if selector_edit_ignored {
+                "ImmutableSelector".into()
+            } else {
+                "Applied".into()
+            }),
+            message: selector_edit_ignored.then(|| {
+                "selector_match_expressions only apply when the Deployment is created; delete it to use the new ones"
+                    .into()
+            }),
+            last_transition_time: None,
+        },
+    );
+    match certificate_condition(&ctx, &ns, &obj.spec).await? {
+        Some(c) => upsert_condition(&mut conditions, c),
+        None => conditions.retain(|c| c.type_ != "CertificateReady"),
+    }
+    for (kind, e) in &failures {
+        warn!("failed to apply {kind} for {ns}/{name}: {e}");
+    }
+    upsert_condition(&mut conditions, partial_failure_condition(&failures));
+    new_status.expires_at =
+        ttl_expiry(&obj.spec, &conditions).map(|at| at.to_rfc3339_opts(SecondsFormat::Secs, true));
     new_status.conditions = Some(conditions);
 
-    let old_status = obj.status.clone().unwrap_or_default();
-    if new_status != old_status {
+    patch_status_if_changed(&obj, &ctx, &ns, new_status).await?;
+
+    // Surfacing the error lets `error_policy` pick the backoff; the hash stays unrecorded so the
+    // next reconcile retries every child.
+    if let Some((_, e)) = failures.into_iter().next() {
+        ctx.in_sync.lock().expect("in_sync lock").remove(&objref);
+        return Err(e);
+    }
+
+    if spec_changed && !ctx.dry_run {
         let api: Api<RustOperator> = Api::namespaced(ctx.client.clone(), &ns);
-        let patch = serde_json::json!({ "status": new_status });
-        api.patch_status(&name, &PatchParams::default(), &Patch::Merge(&patch))
+        let patch = serde_json::json!({
+            "metadata": { "annotations": { LAST_APPLIED_HASH_ANNOTATION: desired_hash } }
+        });
+        api.patch(&name, &PatchParams::default(), &Patch::Merge(&patch))
             .await?;
     }
 
-    Ok(Action::requeue(Duration::from_secs(30)))
+    let wanted = match &obj.spec.autoscaling {
+        Some(a) => a.min_replicas.unwrap_or(1),
+        None => obj.spec.replicas,
+    };
+    let reason = if !config_only && ready < wanted {
+        RequeueReason::Progressing
+    } else {
+        RequeueReason::Steady
+    };
+    Ok(requeue_every(&ctx, reason, resync_interval(&obj)))
 }
 
-fn error_policy(_obj: Arc<RustOperator>, err: &kube::Error, _ctx: Arc<Ctx>) -> Action {
-    error!("reconcile error: {err:?}");
-    Action::requeue(Duration::from_secs(10))
+/// Publishes an Event on the CR. Events are best effort, so failures are only logged.
+async fn publish_event(
+    ctx: &Ctx,
+    obj: &RustOperator,
+    type_: EventType,
+    reason: &str,
+    action: &str,
+    note: String,
+) {
+    if ctx.dry_run {
+        info!("dry-run: would publish {reason} event: {note}");
+        return;
+    }
+    let event = Event {
+        type_,
+        reason: reason.into(),
+        note: Some(note),
+        action: action.into(),
+        secondary: None,
+    };
+    if let Err(e) = ctx.recorder.publish(&event, &obj.object_ref(&())).await {
+        warn!("failed to publish {reason} event: {e}");
+    }
 }
 
-async fn ensure_finalizer(
-    name: &str,
-    ns: &str,
+/// Fast-path check for an unchanged spec, without fetching any child: the last full pass
+/// completed with no child or pod event since (the watches would have dropped the object from
+/// `in_sync`), status still describes that pass, and no TLS Secret is still awaited, since
+/// cert-manager issuing one touches no child.
+fn status_in_sync(obj: &RustOperator, desired: &DesiredState, ctx: &Ctx) -> bool {
+    let Some(status) = &obj.status else {
+        return false;
+    };
+    ctx.in_sync
+        .lock()
+        .expect("in_sync lock")
+        .contains(&ObjectRef::from_obj(obj))
+        && status.up_to_date == Some(true)
+        && status.observed_generation == obj.meta().generation
+        && status.managed_resources.as_ref() == Some(&desired.managed_refs())
+        && recorded_condition(status, "CertificateReady").is_none_or(|c| c.status == "True")
+}
+
+/// `CertificateReady` for the Ingress TLS Secret (which cert-manager may not have issued yet);
+/// `None` when the Ingress has no TLS.
+async fn certificate_condition(
     ctx: &Ctx,
-    present: bool,
-) -> Result<(), kube::Error> {
-    let api: Api<RustOperator> = Api::namespaced(ctx.client.clone(), ns);
-    if present {
-        let patch = serde_json::json!({ "metadata": { "finalizers": [FINALIZER] }});
-        api.patch(name, &PatchParams::default(), &Patch::Merge(&patch))
-            .await?;
-    } else {
-        let patch = serde_json::json!({ "metadata": { "finalizers": [] }});
-        api.patch(name, &PatchParams::default(), &Patch::Merge(&patch))
-            .await?;
+    ns: &str,
+    spec: &RustOperatorSpec,
+) -> Result<Option<HwCondition>, kube::Error> {
+    let secret_name = spec.tls_secret_name.trim();
+    if ingress_hosts(spec).is_empty() || secret_name.is_empty() {
+        return Ok(None);
+    }
+    let secret = Api::<Secret>::namespaced(ctx.client.clone(), ns)
+        .get_opt(secret_name)
+        .await?;
+    let ready = tls_secret_ready(secret.as_ref());
+    Ok(Some(HwCondition {
+        type_: "CertificateReady".into(),
+        status: if ready { "True".into() } else { "False".into() },
+        reason: Some(if ready {
+            "SecretReady".into()
+        } else {
+            "SecretMissing".into()
+        }),
+        message: (!ready)
+            .then(|| format!("TLS secret {secret_name} does not exist or lacks tls.crt/tls.key")),
+        last_transition_time: None,
+    }))
+}
+
+/// Deletes children labelled for this CR whose owner reference carries `uid` but which are not
+/// the object we just applied (`keep`). Anything not owned by this exact CR instance is left alone.
+async fn prune_owned<K>(
+    api: &Api<K>,
+    selector: &str,
+    uid: &str,
+    keep: Option<String>,
+    dp: &DeleteParams,
+) -> Result<(), kube::Error>
+where
+    K: Resource<DynamicType = ()> + Clone + DeserializeOwned + std::fmt::Debug,
+{
+    let existing = api.list(&ListParams::default().labels(selector)).await?;
+    for child in existing.items {
+        let child_name = child.name_any();
+        if keep.as_deref() == Some(child_name.as_str()) || !is_owned_by(child.meta(), uid) {
+            continue;
+        }
+        info!("pruning stale {} {child_name}", K::kind(&()));
+        let _ = api.delete(&child_name, dp).await.ok();
     }
     Ok(())
 }
 
-async fn cleanup_children(name: &str, ns: &str, ctx: &Ctx) -> Result<(), kube::Error> {
-    let deploys: Api<Deployment> = Api::namespaced(ctx.client.clone(), ns);
-    let svcs: Api<Service> = Api::namespaced(ctx.client.clone(), ns);
-    let cms: Api<ConfigMap> = Api::namespaced(ctx.client.clone(), ns);
-    let _ = deploys.delete(name, &Default::default()).await;
-    let _ = svcs
-        .delete(&format!("{name}-service"), &Default::default())
+/// Fields a dry-run apply would change, ignoring server-managed metadata and status.
+fn changed_fields<K: Serialize>(current: &K, applied: &K) -> Vec<String> {
+    let (Ok(current), Ok(applied)) = (serde_json::to_value(current), serde_json::to_value(applied))
+    else {
+        return Vec::new();
+    };
+    let mut fields: Vec<String> = ["labels", "annotations", "ownerReferences"]
+        .into_iter()
+        .filter(|k| current["metadata"].get(k) != applied["metadata"].get(k))
+        .map(|k| format!("metadata.{k}"))
+        .collect();
+    if let Some(obj) = applied.as_object() {
+        fields.extend(
+            obj.keys()
+                .filter(|k| !matches!(k.as_str(), "metadata" | "status"))
+                .filter(|k| current.get(k.as_str()) != obj.get(k.as_str()))
+                .cloned(),
+        );
+    }
+    fields
+}
+
+/// Returns the API message when an apply was rejected because it changes an immutable field.
+fn immutable_field_error(err: &kube::Error) -> Option<&str> {
+    match err {
+        kube::Error::Api(resp)
+            if resp.code == 422 && resp.message.contains("field is immutable") =>
+        {
+            Some(&resp.message)
+        }
+        _ => None,
+    }
+}
+
+/// Server-side applies a child. Retrying an immutable-field change can never succeed, so such a
+/// rejection either deletes and recreates the child (when `recreate_on_immutable_change` is set)
+/// or records why and returns `None` so the caller waits for the spec to change. Any other error
+/// is passed through to `error_policy`.
+#[tracing::instrument(name = "apply_child", skip_all, fields(kind = %K::kind(&()), name = child))]
+async fn apply_child<K>(
+    api: &Api<K>,
+    child: &str,
+    desired: &K,
+    obj: &RustOperator,
+    ctx: &Ctx,
+    ns: &str,
+) -> Result<Option<K>, kube::Error>
+where
+    K: Resource<DynamicType = ()>
+        + Clone
+        + DeserializeOwned
+        + Serialize
+        + std::fmt::Debug
+        + Send
+        + 'static,
+{
+    let kind = K::kind(&());
+    let mut desired = desired.clone();
+    let current = if ctx.dry_run || !ctx.preserved_annotation_prefixes.is_empty() {
+        api.get_opt(child).await?
+    } else {
+        None
+    };
+    if let Some(current) = &current {
+        preserve_annotations(
+            desired.meta_mut(),
+            current.meta(),
+            &ctx.preserved_annotation_prefixes,
+        );
+    }
+    let desired = &desired;
+
+    let mut params = PatchParams::apply("rust-operator");
+    params.force = obj.spec.force_apply;
+    params.dry_run = ctx.dry_run;
+    let err = match api.patch(child, &params, &Patch::Apply(desired)).await {
+        Ok(applied) => {
+            if ctx.dry_run {
+                match &current {
+                    None => info!("dry-run: would create {kind} {ns}/{child}"),
+                    Some(current) => match changed_fields(current, &applied).as_slice() {
+                        [] => debug!("dry-run: {kind} {ns}/{child} unchanged"),
+                        fields => info!("dry-run: would change {kind} {ns}/{child}: {fields:?}"),
+                    },
+                }
+            }
+            return Ok(Some(applied));
+        }
+        Err(e) => e,
+    };
+    if let Some(detail) = apply_conflict_error(&err) {
+        let managers = conflicting_managers(detail);
+        warn!("{kind} {ns}/{child} has fields owned by {managers:?}, not forcing: {detail}");
+        let condition = HwCondition {
+            type_: "Degraded".into(),
+            status: "True".into(),
+            reason: Some("FieldConflict".into()),
+            message: Some(format!(
+                "{kind} {child} has fields managed by {}: {detail}",
+                managers.join(", ")
+            )),
+            last_transition_time: None,
+        };
+        report_blocked(obj, ctx, ns, condition).await?;
+        return Ok(None);
+    }
+    let Some(detail) = immutable_field_error(&err) else {
+        return Err(err);
+    };
+
+    if obj.spec.recreate_on_immutable_change && ctx.dry_run {
+        info!("dry-run: would recreate {kind} {ns}/{child} to change an immutable field: {detail}");
+        return Ok(None);
+    }
+    if obj.spec.recreate_on_immutable_change {
+        warn!(
+            "recreating {kind} {ns}/{child} to change an immutable field, expect brief downtime: {detail}"
+        );
+        publish_event(
+            ctx,
+            obj,
+            EventType::Warning,
+            "Recreating",
+            "Recreate",
+            format!(
+                "recreating {kind} {child} to change an immutable field; expect brief downtime"
+            ),
+        )
         .await;
-    let _ = cms.delete(name, &Default::default()).await;
-    Ok(())
+        if let Some(old) = api.delete(child, &DeleteParams::background()).await?.left()
+            && let Some(uid) = old.uid()
+        {
+            let gone = await_condition(api.clone(), child, conditions::is_deleted(&uid));
+            let _ = tokio::time::timeout(Duration::from_secs(30), gone).await;
+        }
+        return api
+            .patch(child, &params, &Patch::Apply(desired))
+            .await
+            .map(Some);
+    }
+
+    warn!("{kind} {ns}/{child} has an immutable field change: {detail}");
+    let condition = HwCondition {
+        type_: "ImmutableFieldChanged".into(),
+        status: "True".into(),
+        reason: Some("RecreateRequired".into()),
+        message: Some(format!(
+            "{kind} {child} must be deleted and recreated to apply this change: {detail}"
+        )),
+        last_transition_time: None,
+    };
+    report_blocked(obj, ctx, ns, condition).await?;
+    Ok(None)
+}
+
+/// Returns the API message when a non-forced apply hit fields owned by another manager.
+fn apply_conflict_error(err: &kube::Error) -> Option<&str> {
+    match err {
+        kube::Error::Api(resp) if resp.code == 409 && resp.reason == "Conflict" => {
+            Some(&resp.message)
+        }
+        _ => None,
+    }
+}
+
+/// Field managers named in an apply conflict message, e.g.
+/// `conflict with "kubectl-edit" using apps/v1: .spec.replicas`.
+fn conflicting_managers(message: &str) -> Vec<&str> {
+    let mut managers: Vec<&str> = message
+        .split("conflict with \"")
+        .skip(1)
+        .filter_map(|rest| rest.split('"').next())
+        .collect();
+    managers.sort_unstable();
+    managers.dedup();
+    managers
+}
+
+/// Records a condition explaining why the spec could not be applied.
+async fn report_blocked(
+    obj: &RustOperator,
+    ctx: &Ctx,
+    ns: &str,
+    condition: HwCondition,
+) -> Result<(), kube::Error> {
+    let mut new_status = obj.status.clone().unwrap_or_default();
+    let mut conditions = new_status.conditions.take().unwrap_or_default();
+    upsert_condition(&mut conditions, condition);
+    new_status.conditions = Some(conditions);
+    new_status.up_to_date = Some(false);
+    patch_status_if_changed(obj, ctx, ns, new_status).await
+}
+
+/// `Degraded` condition; `Some(msg)` marks the spec invalid, `None` clears it.
+fn degraded_condition(invalid: Option<String>) -> HwCondition {
+    HwCondition {
+        type_: "Degraded".into(),
+        status: if invalid.is_some() {
+            "True".into()
+        } else {
+            "False".into()
+        },
+        reason: Some(if invalid.is_some() {
+            "InvalidSpec".into()
+        } else {
+            "SpecValid".into()
+        }),
+        message: invalid,
+        last_transition_time: None,
+    }
+}
+
+/// `Available` and `Progressing` derived from the Deployment's available and updated counts.
+fn rollout_conditions(d: &Deployment) -> [HwCondition; 2] {
+    let st = d.status.clone().unwrap_or_default();
+    let available = st.available_replicas.unwrap_or(0);
+    let updated = st.updated_replicas.unwrap_or(0);
+    let desired = d.spec.as_ref().and_then(|s| s.replicas).unwrap_or(1);
+    let rolled_out = updated == desired;
+    [
+        HwCondition {
+            type_: "Available".into(),
+            status: if available >= 1 {
+                "True".into()
+            } else {
+                "False".into()
+            },
+            reason: Some(if available >= 1 {
+                "MinimumReplicasAvailable".into()
+            } else {
+                "NoReplicasAvailable".into()
+            }),
+            message: Some(format!("available_replicas={available}")),
+            last_transition_time: None,
+        },
+        HwCondition {
+            type_: "Progressing".into(),
+            status: if rolled_out {
+                "False".into()
+            } else {
+                "True".into()
+            },
+            reason: Some(if rolled_out {
+                "RolloutComplete".into()
+            } else {
+                "RollingOut".into()
+            }),
+            message: Some(format!("updated_replicas={updated}/{desired}")),
+            last_transition_time: None,
+        },
+    ]
+}
+
+/// The `type_` condition as status records it, without its transition time, for comparing
+/// against a freshly derived one.
+fn recorded_condition(status: &RustOperatorStatus, type_: &str) -> Option<HwCondition> {
+    status
+        .conditions
+        .iter()
+        .flatten()
+        .find(|c| c.type_ == type_)
+        .map(|c| HwCondition {
+            last_transition_time: None,
+            ..c.clone()
+        })
+}
+
+/// `PartialFailure` condition listing the kinds whose apply failed in this reconcile.
+fn partial_failure_condition(failures: &[(&str, kube::Error)]) -> HwCondition {
+    HwCondition {
+        type_: "PartialFailure".into(),
+        status: if failures.is_empty() {
+            "False".into()
+        } else {
+            "True".into()
+        },
+        reason: Some(if failures.is_empty() {
+            "AllApplied".into()
+        } else {
+            "ApplyFailed".into()
+        }),
+        message: (!failures.is_empty()).then(|| {
+            failures
+                .iter()
+                .map(|(kind, e)| format!("{kind}: {e}"))
+                .collect::<Vec<_>>()
+                .join("; ")
+        }),
+        last_transition_time: None,
+    }
+}
+
+/// `Paused` condition; True while `replicas` is 0 on purpose.
+fn paused_condition(scaled_to_zero: bool) -> HwCondition {
+    HwCondition {
+        type_: "Paused".into(),
+        status: if scaled_to_zero {
+            "True".into()
+        } else {
+            "False".into()
+        },
+        reason: Some(if scaled_to_zero {
+            "ScaledToZero".into()
+        } else {
+            "Running".into()
+        }),
+        message: scaled_to_zero.then(|| "spec.replicas is 0".into()),
+        last_transition_time: None,
+    }
+}
+
+/// `PolicyViolation` condition; `Some(msg)` blocks the CR, `None` clears it.
+fn policy_condition(violation: Option<String>) -> HwCondition {
+    HwCondition {
+        type_: "PolicyViolation".into(),
+        status: if violation.is_some() {
+            "True".into()
+        } else {
+            "False".into()
+        },
+        reason: Some(if violation.is_some() {
+            "MissingRequiredLabels".into()
+        } else {
+            "Compliant".into()
+        }),
+        message: violation,
+        last_transition_time: None,
+    }
+}
+
+async fn patch_status_if_changed(
+    obj: &RustOperator,
+    ctx: &Ctx,
+    ns: &str,
+    new_status: RustOperatorStatus,
+) -> Result<(), kube::Error> {
+    let old_status = obj.status.clone().unwrap_or_default();
+    if new_status == old_status {
+        return Ok(());
+    }
+    if ctx.dry_run {
+        info!(
+            "dry-run: would patch status of {ns}/{}: {}",
+            obj.name_any(),
+            serde_json::to_string(&new_status).unwrap_or_default()
+        );
+        return Ok(());
+    }
+    let api: Api<RustOperator> = Api::namespaced(ctx.client.clone(), ns);
+    let (params, patch) = status_patch(&obj.spec.status_patch_strategy, &new_status);
+    api.patch_status(&obj.name_any(), &params, &patch).await?;
+    Ok(())
+}
+
+/// Merge patches are the default; "apply" uses server-side apply under a status-only field
+/// manager so fields written by other controllers are not clobbered.
+fn status_patch(
+    strategy: &str,
+    status: &RustOperatorStatus,
+) -> (PatchParams, Patch<serde_json::Value>) {
+    if strategy == "apply" {
+        let body = serde_json::json!({
+            "apiVersion": RustOperator::api_version(&()),
+            "kind": RustOperator::kind(&()),
+            "status": status,
+        });
+        (
+            PatchParams::apply("rust-operator-status").force(),
+            Patch::Apply(body),
+        )
+    } else {
+        let body = serde_json::json!({ "status": status });
+        (PatchParams::default(), Patch::Merge(body))
+    }
+}
+
+fn error_policy(
+    obj: Arc<RustOperator>,
+    err: &finalizer::Error<kube::Error>,
+    ctx: Arc<Ctx>,
+) -> Action {
+    error!("reconcile error: {err:?}");
+    ctx.metrics.reconcile_errors.inc();
+    let api_code = match err {
+        finalizer::Error::ApplyFailed(kube::Error::Api(resp))
+        | finalizer::Error::CleanupFailed(kube::Error::Api(resp))
+        | finalizer::Error::AddFinalizer(kube::Error::Api(resp))
+        | finalizer::Error::RemoveFinalizer(kube::Error::Api(resp)) => Some(resp.code),
+        _ => None,
+    };
+    if api_code == Some(429) {
+        // Publishing an Event would only add to the load the API server is shedding.
+        return requeue(&ctx, RequeueReason::Throttled);
+    }
+    let objref = ObjectRef::from_obj(&*obj);
+    let note = err.to_string();
+    let event_ctx = ctx.clone();
+    tokio::spawn(async move {
+        publish_event(
+            &event_ctx,
+            &obj,
+            EventType::Warning,
+            "ReconcileFailed",
+            "Reconcile",
+            note,
+        )
+        .await;
+    });
+    match api_code {
+        Some(409) => requeue(&ctx, RequeueReason::Conflict),
+        _ => {
+            let failures = {
+                let mut counts = ctx.failures.lock().expect("failures lock");
+                let n = counts.entry(objref).or_default();
+                *n = n.saturating_add(1);
+                *n
+            };
+            requeue_after_error(&ctx, failures)
+        }
+    }
+}
+
+/// Deletes `child` only when this CR owns it, so a same-named object someone else created
+/// (e.g. the Deployment a config-only user brings) survives. Errors are ignored like the deletes.
+async fn delete_owned<K>(api: &Api<K>, child: &str, uid: &str, dp: &DeleteParams)
+where
+    K: Resource<DynamicType = ()> + Clone + DeserializeOwned + std::fmt::Debug,
+{
+    if let Ok(Some(existing)) = api.get_opt(child).await
+        && is_owned_by(existing.meta(), uid)
+    {
+        let _ = api.delete(child, dp).await;
+    }
+}
+
+async fn cleanup_children(obj: &RustOperator, ns: &str, ctx: &Ctx) -> Result<(), kube::Error> {
+    let name = &obj.name_any();
+    // Without a uid the CR cannot own anything.
+    let Some(uid) = obj.uid() else {
+        return Ok(());
+    };
+    let deploys: Api<Deployment> = Api::namespaced(ctx.client.clone(), ns);
+    let svcs: Api<Service> = Api::namespaced(ctx.client.clone(), ns);
+    let cms: Api<ConfigMap> = Api::namespaced(ctx.client.clone(), ns);
+    let hpas: Api<HorizontalPodAutoscaler> = Api::namespaced(ctx.client.clone(), ns);
+    let pdbs: Api<PodDisruptionBudget> = Api::namespaced(ctx.client.clone(), ns);
+    let netpols: Api<NetworkPolicy> = Api::namespaced(ctx.client.clone(), ns);
+    let dp = ctx.delete_params();
+    delete_owned(&hpas, name, &uid, &dp).await;
+    delete_owned(&pdbs, name, &uid, &dp).await;
+    delete_owned(&netpols, name, &uid, &dp).await;
+    delete_owned(&deploys, name, &uid, &dp).await;
+    delete_owned(&svcs, &format!("{name}-service"), &uid, &dp).await;
+    delete_owned(&cms, name, &uid, &dp).await;
+    // Only an account this CR created; a referenced one belongs to someone else.
+    if obj.spec.create_service_account
+        && let Some(sa) = service_account_name(&obj.spec)
+    {
+        let sas: Api<ServiceAccount> = Api::namespaced(ctx.client.clone(), ns);
+        delete_owned(&sas, sa, &uid, &dp).await;
+    }
+    Ok(())
+}
+
+/// `SelectorMismatch` condition; True when the Service would select none of the pods.
+fn selector_condition(selector_ok: bool) -> HwCondition {
+    HwCondition {
+        type_: "SelectorMismatch".into(),
+        status: if selector_ok {
+            "False".into()
+        } else {
+            "True".into()
+        },
+        reason: Some(if selector_ok {
+            "SelectorMatches".into()
+        } else {
+            "ServiceSelectsNoPods".into()
+        }),
+        message: (!selector_ok)
+            .then(|| "service selector is not a subset of the pod template labels".into()),
+        last_transition_time: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        crd::{RustOperatorSpec, RustOperatorStatus},
+        fake_api::FakeApi,
+    };
+
+    const CR_PATH: &str = "/apis/rootster.xyz/v1/namespaces/default/rustoperators/web";
+    const PODS_PATH: &str = "/api/v1/namespaces/default/pods";
+    const SVC_PATH: &str = "/api/v1/namespaces/default/services/web-service";
+    const DEPLOY_PATH: &str = "/apis/apps/v1/namespaces/default/deployments/web";
+
+    /// Stores a `web` RustOperator with `spec` (over `message: hello`) and returns it as a
+    /// reconcile would receive it.
+    fn operator(api: &FakeApi, spec: serde_json::Value) -> Arc<RustOperator> {
+        let mut full = json!({ "message": "hello" });
+        full.as_object_mut()
+            .unwrap()
+            .extend(spec.as_object().unwrap().clone());
+        api.insert(
+            CR_PATH,
+            json!({
+                "apiVersion": "rootster.xyz/v1",
+                "kind": "RustOperator",
+                "metadata": {
+                    "name": "web",
+                    "namespace": "default",
+                    "uid": "uid-1",
+                    "generation": 1,
+                },
+                "spec": full,
+            }),
+        );
+        stored(api)
+    }
+
+    /// The CR as the fake API server currently has it.
+    fn stored(api: &FakeApi) -> Arc<RustOperator> {
+        Arc::new(serde_json::from_value(api.get(CR_PATH).unwrap()).unwrap())
+    }
+
+    fn status(api: &FakeApi) -> RustOperatorStatus {
+        stored(api).status.clone().unwrap_or_default()
+    }
+
+    fn condition(status: &RustOperatorStatus, type_: &str) -> HwCondition {
+        recorded_condition(status, type_).unwrap()
+    }
+
+    fn ctx(api: &FakeApi) -> Ctx {
+        let client = api.client();
+        Ctx {
+            recorder: Recorder::new(client.clone(), "rust-operator".into()),
+            client,
+            metrics: Metrics::new(),
+            preserved_annotation_prefixes: Vec::new(),
+            finalizer: None,
+            legacy_finalizers: Vec::new(),
+            dry_run: false,
+            required_labels: Vec::new(),
+            propagate_required_labels: false,
+            failures: Arc::default(),
+            in_sync: InSync::default(),
+            reconcile_timeout: DEFAULT_RECONCILE_TIMEOUT,
+        }
+    }
+
+    fn add_pod(api: &FakeApi, name: &str, phase: &str) {
+        api.insert(
+            &format!("{PODS_PATH}/{name}"),
+            json!({
+                "apiVersion": "v1",
+                "kind": "Pod",
+                "metadata": { "name": name, "namespace": "default", "labels": labels("web") },
+                "status": { "phase": phase },
+            }),
+        );
+    }
+
+    #[tokio::test]
+    async fn each_requeue_reason_increments_its_label() {
+        let ctx = ctx(&FakeApi::new());
+        let reasons = [
+            RequeueReason::Steady,
+            RequeueReason::Progressing,
+            RequeueReason::Error,
+            RequeueReason::Conflict,
+            RequeueReason::Throttled,
+            RequeueReason::Paused,
+        ];
+        for (i, reason) in reasons.into_iter().enumerate() {
+            for _ in 0..=i {
+                requeue(&ctx, reason);
+            }
+        }
+        requeue_after_error(&ctx, 3);
+        let count = |label| ctx.metrics.requeues.with_label_values(&[label]).get();
+        assert_eq!(count("steady"), 1);
+        assert_eq!(count("progressing"), 2);
+        assert_eq!(count("error"), 3 + 1);
+        assert_eq!(count("conflict"), 4);
+        assert_eq!(count("throttled"), 5);
+        assert_eq!(count("paused"), 6);
+    }
+
+    #[test]
+    fn status_patch_strategy_picks_the_patch_type() {
+        let status = RustOperatorStatus {
+            ready_replicas: Some(1),
+            ..Default::default()
+        };
+        let (params, patch) = status_patch("merge", &status);
+        assert_eq!(params.field_manager, None);
+        assert!(matches!(patch, Patch::Merge(body) if body["status"]["ready_replicas"] == 1));
+
+        let (params, patch) = status_patch("apply", &status);
+        assert_eq!(
+            params.field_manager.as_deref(),
+            Some("rust-operator-status")
+        );
+        assert!(params.force);
+        let Patch::Apply(body) = patch else {
+            panic!("expected an apply patch");
+        };
+        assert_eq!(body["kind"], "RustOperator");
+        assert_eq!(body["apiVersion"], "rootster.xyz/v1");
+        assert_eq!(body["status"]["ready_replicas"], 1);
+    }
+
+    #[test]
+    fn mismatched_selector_trips_the_condition() {
+        let owner = k8s_openapi::apimachinery::pkg::apis::meta::v1::OwnerReference {
+            uid: "uid-1".into(),
+            ..Default::default()
+        };
+        let spec: RustOperatorSpec = serde_json::from_value(json!({ "message": "hi" })).unwrap();
+        let state = build_desired_state(&spec, "web", Some(1), owner).unwrap();
+        let svc = state.service.unwrap();
+        let mut deploy = state.deployment.unwrap();
+        let ok = service_selects_pods(&svc, &deploy);
+        assert_eq!(selector_condition(ok).status, "False");
+
+        let template = deploy.spec.as_mut().unwrap().template.metadata.as_mut();
+        template.unwrap().labels = Some(labels("other"));
+        let mismatch = selector_condition(service_selects_pods(&svc, &deploy));
+        assert_eq!(mismatch.status, "True");
+        assert_eq!(mismatch.reason.as_deref(), Some("ServiceSelectsNoPods"));
+    }
+
+    #[tokio::test]
+    async fn two_pods_produce_two_status_entries() {
+        let api = FakeApi::new();
+        let obj = operator(&api, json!({ "replicas": 2 }));
+        add_pod(&api, "web-b", "Pending");
+        add_pod(&api, "web-a", "Running");
+
+        apply(obj, Arc::new(ctx(&api))).await.unwrap();
+
+        let pods = status(&api).pod_statuses.unwrap();
+        let entries: Vec<_> = pods
+            .iter()
+            .map(|p| (p.name.as_str(), p.phase.as_str()))
+            .collect();
+        assert_eq!(entries, [("web-a", "Running"), ("web-b", "Pending")]);
+        assert_eq!(api.calls_to("GET", PODS_PATH), 1);
+        let ready = condition(&status(&api), "Ready");
+        assert_eq!(ready.message.as_deref(), Some("ready_replicas=0/2"));
+    }
+
+    /// Reasons of the Events published so far, in creation order.
+    fn event_reasons(api: &FakeApi) -> Vec<String> {
+        let mut events = api.items("/apis/events.k8s.io/v1/namespaces/default/events");
+        events.sort_by_key(|e| {
+            e["metadata"]["resourceVersion"]
+                .as_str()
+                .unwrap()
+                .parse::<u64>()
+                .unwrap()
+        });
+        events
+            .iter()
+            .map(|e| e["reason"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn lifecycle_events_are_published_on_the_cr() {
+        let api = FakeApi::new();
+        let obj = operator(&api, json!({ "ingress_host": "web.example.com" }));
+        apply(obj.clone(), Arc::new(ctx(&api))).await.unwrap();
+
+        assert_eq!(
+            event_reasons(&api),
+            ["ConfigMapApplied", "DeploymentScaled", "IngressCreated"]
+        );
+        let events = api.items("/apis/events.k8s.io/v1/namespaces/default/events");
+        assert!(events.iter().all(|e| e["regarding"]["uid"] == "uid-1"));
+        assert!(events.iter().all(|e| e["type"] == "Normal"));
+    }
+
+    #[tokio::test]
+    async fn missing_tls_secret_makes_certificate_not_ready() {
+        let api = FakeApi::new();
+        let ctx = Arc::new(ctx(&api));
+        let obj = operator(
+            &api,
+            json!({ "ingress_host": "web.example.com", "tls_secret_name": "web-tls" }),
+        );
+        apply(obj, ctx.clone()).await.unwrap();
+        let cert = condition(&status(&api), "CertificateReady");
+        assert_eq!(cert.status, "False");
+        assert_eq!(cert.reason.as_deref(), Some("SecretMissing"));
+
+        api.insert(
+            "/api/v1/namespaces/default/secrets/web-tls",
+            json!({
+                "apiVersion": "v1",
+                "kind": "Secret",
+                "metadata": { "name": "web-tls" },
+                "data": { "tls.crt": "Y3J0", "tls.key": "a2V5" },
+            }),
+        );
+        apply(stored(&api), ctx.clone()).await.unwrap();
+        assert_eq!(condition(&status(&api), "CertificateReady").status, "True");
+
+        // Without TLS there is nothing to report.
+        apply(edit_spec(&api, json!({ "tls_secret_name": "" })), ctx)
+            .await
+            .unwrap();
+        assert_eq!(recorded_condition(&status(&api), "CertificateReady"), None);
+    }
+
+    #[tokio::test]
+    async fn namespace_default_image_applies_when_the_cr_omits_it() {
+        let image = |api: &FakeApi| {
+            api.get(DEPLOY_PATH).unwrap()["spec"]["template"]["spec"]["containers"][0]["image"]
+                .clone()
+        };
+        for (spec, expected) in [
+            (json!({}), "registry.local/nginx:1.27-alpine"),
+            (json!({ "image": "nginx" }), "nginx:1.27-alpine"),
+        ] {
+            let api = FakeApi::new();
+            api.insert(
+                &format!("/api/v1/namespaces/default/configmaps/{NAMESPACE_DEFAULTS_CONFIGMAP}"),
+                json!({
+                    "apiVersion": "v1",
+                    "kind": "ConfigMap",
+                    "metadata": { "name": NAMESPACE_DEFAULTS_CONFIGMAP },
+                    "data": { "image": "registry.local/nginx" },
+                }),
+            );
+            apply(operator(&api, spec), Arc::new(ctx(&api)))
+                .await
+                .unwrap();
+            assert_eq!(image(&api), expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn requeue_seconds_sets_the_returned_action() {
+        let api = FakeApi::new();
+        let obj = operator(&api, json!({ "requeue_seconds": 120 }));
+        let action = apply(obj, Arc::new(ctx(&api))).await.unwrap();
+        assert_eq!(action, Action::requeue(Duration::from_secs(120)));
+
+        for unset in [json!({ "requeue_seconds": 0 }), json!({})] {
+            assert_eq!(resync_interval(&operator(&api, unset)), DEFAULT_RESYNC);
+        }
+    }
+
+    #[tokio::test]
+    async fn match_expressions_apply_on_create_only() {
+        let api = FakeApi::new();
+        let ctx = Arc::new(ctx(&api));
+        let expr = |values| json!([{ "key": "tier", "operator": "In", "values": values }]);
+        let obj = operator(&api, json!({ "selector_match_expressions": expr(["web"]) }));
+        apply(obj, ctx.clone()).await.unwrap();
+        let selector =
+            || api.get(DEPLOY_PATH).unwrap()["spec"]["selector"]["matchExpressions"].clone();
+        assert_eq!(selector(), expr(["web"]));
+        let edited = condition(&status(&api), "SelectorExpressionsChanged");
+        assert_eq!(edited.status, "False");
+
+        let obj = edit_spec(&api, json!({ "selector_match_expressions": expr(["api"]) }));
+        apply(obj, ctx).await.unwrap();
+        assert_eq!(selector(), expr(["web"]));
+        let edited = condition(&status(&api), "SelectorExpressionsChanged");
+        assert_eq!(edited.status, "True");
+        assert_eq!(edited.reason.as_deref(), Some("ImmutableSelector"));
+    }
+
+    #[tokio::test]
+    async fn finalizer_splits_apply_and_cleanup() {
+        let api = FakeApi::new();
+        let mut ctx = ctx(&api);
+        ctx.finalizer = Some(FINALIZER.into());
+        let ctx = Arc::new(ctx);
+
+        // The first pass only adds the finalizer; the patch triggers the next reconcile.
+        reconcile(operator(&api, json!({})), ctx.clone())
+            .await
+            .unwrap();
+        assert_eq!(stored(&api).finalizers(), [FINALIZER]);
+        assert!(api.get(DEPLOY_PATH).is_none());
+
+        reconcile(stored(&api), ctx.clone()).await.unwrap();
+        assert!(api.get(DEPLOY_PATH).is_some());
+        assert!(api.get(SVC_PATH).is_some());
+        assert_eq!(ctx.in_sync.lock().unwrap().len(), 1);
+
+        let mut cr = api.get(CR_PATH).unwrap();
+        cr["metadata"]["deletionTimestamp"] = json!("2026-01-01T00:00:00Z");
+        api.insert(CR_PATH, cr);
+        reconcile(stored(&api), ctx.clone()).await.unwrap();
+        assert!(api.get(DEPLOY_PATH).is_none());
+        assert!(api.get(SVC_PATH).is_none());
+        assert!(stored(&api).finalizers().is_empty());
+        assert!(ctx.in_sync.lock().unwrap().is_empty());
+        assert!(ctx.failures.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn dry_run_leaves_the_cluster_untouched() {
+        let api = FakeApi::new();
+        let obj = operator(&api, json!({ "ingress_host": "web.example.com" }));
+        api.insert(
+            SVC_PATH,
+            child("v1", "Service", "web-service", Some("uid-1")),
+        );
+        let mut stale = child("networking.k8s.io/v1", "Ingress", "old", Some("uid-1"));
+        stale["metadata"]["name"] = json!("old");
+        let stale_path = "/apis/networking.k8s.io/v1/namespaces/default/ingresses/old";
+        api.insert(stale_path, stale);
+        let before = api.get(CR_PATH);
+        let mut ctx = ctx(&api);
+        ctx.dry_run = true;
+
+        apply(obj, Arc::new(ctx)).await.unwrap();
+
+        // Applies and deletes were sent, but only as dry runs.
+        assert!(api.calls_to("PATCH", "/deployments/") > 0);
+        assert!(api.get(DEPLOY_PATH).is_none());
+        assert!(
+            api.get("/api/v1/namespaces/default/configmaps/web")
+                .is_none()
+        );
+        assert!(api.get(SVC_PATH).unwrap()["spec"].is_null());
+        assert!(api.get(stale_path).is_some());
+        // No status, annotation or Event writes.
+        assert_eq!(api.get(CR_PATH), before);
+        assert_eq!(api.calls_to("POST", "/events"), 0);
+    }
+
+    #[tokio::test]
+    async fn freshly_reconciled_object_is_up_to_date() {
+        let api = FakeApi::new();
+        let obj = operator(&api, json!({}));
+        assert_eq!(obj.status, None);
+
+        apply(obj, Arc::new(ctx(&api))).await.unwrap();
+
+        assert_eq!(status(&api).up_to_date, Some(true));
+    }
+
+    #[tokio::test]
+    async fn unchanged_object_takes_the_fast_path() {
+        let api = FakeApi::new();
+        let ctx = Arc::new(ctx(&api));
+        apply(operator(&api, json!({})), ctx.clone()).await.unwrap();
+        api.clear_calls();
+
+        apply(stored(&api), ctx.clone()).await.unwrap();
+
+        // Zero child fetches: only the namespace defaults the hash is computed from.
+        assert_eq!(
+            api.calls(),
+            [format!(
+                "GET /api/v1/namespaces/default/configmaps/{NAMESPACE_DEFAULTS_CONFIGMAP}"
+            )]
+        );
+
+        // The Pod watch takes the object off the fast path, so the next pass is a full one.
+        add_pod(&api, "web-a", "Running");
+        let pod: Pod =
+            serde_json::from_value(api.get(&format!("{PODS_PATH}/web-a")).unwrap()).unwrap();
+        assert_eq!(
+            mark_changed(&ctx.in_sync, pod_to_owner)(pod),
+            [ObjectRef::new("web").within("default")]
+        );
+        api.clear_calls();
+        apply(stored(&api), ctx).await.unwrap();
+        assert!(api.calls_to("PATCH", "/deployments/") > 0);
+        assert_eq!(status(&api).pod_statuses.unwrap().len(), 1);
+    }
+
+    /// Applies `edit` to the stored CR's spec and bumps its generation, like a user edit.
+    fn edit_spec(api: &FakeApi, edit: serde_json::Value) -> Arc<RustOperator> {
+        let mut cr = api.get(CR_PATH).unwrap();
+        let generation = cr["metadata"]["generation"].as_i64().unwrap();
+        cr["metadata"]["generation"] = json!(generation + 1);
+        cr["spec"]
+            .as_object_mut()
+            .unwrap()
+            .extend(edit.as_object().unwrap().clone());
+        api.insert(CR_PATH, cr);
+        stored(api)
+    }
+
+    #[tokio::test]
+    async fn observed_generation_tracks_full_reconciles() {
+        let api = FakeApi::new();
+        let ctx = Arc::new(ctx(&api));
+        apply(operator(&api, json!({})), ctx.clone()).await.unwrap();
+        assert_eq!(status(&api).observed_generation, Some(1));
+
+        let obj = edit_spec(&api, json!({ "replicas": 3 }));
+        apply(obj, ctx.clone()).await.unwrap();
+        assert_eq!(status(&api).observed_generation, Some(2));
+
+        // A child that fails to apply leaves the generation unobserved.
+        let obj = edit_spec(&api, json!({ "replicas": 4 }));
+        api.fail_once(
+            http::Method::PATCH,
+            DEPLOY_PATH,
+            500,
+            "InternalError",
+            "etcd timeout",
+        );
+        assert!(apply(obj, ctx).await.is_err());
+        let st = status(&api);
+        assert_eq!(st.observed_generation, Some(2));
+        assert_eq!(st.up_to_date, Some(false));
+    }
+
+    #[tokio::test]
+    async fn owner_reference_controller_flag_follows_the_spec() {
+        for controller_owner in [true, false] {
+            let api = FakeApi::new();
+            let obj = operator(&api, json!({ "controller_owner": controller_owner }));
+            apply(obj, Arc::new(ctx(&api))).await.unwrap();
+
+            let owner = &api.get(DEPLOY_PATH).unwrap()["metadata"]["ownerReferences"][0];
+            assert_eq!(owner["uid"], "uid-1");
+            assert_eq!(
+                owner["controller"].as_bool(),
+                controller_owner.then_some(true)
+            );
+        }
+    }
+
+    fn deployment(owner_uid: Option<&str>) -> serde_json::Value {
+        child("apps/v1", "Deployment", "web", owner_uid)
+    }
+
+    /// A labelled child, owned by the CR with `owner_uid` if given.
+    fn child(
+        api_version: &str,
+        kind: &str,
+        name: &str,
+        owner_uid: Option<&str>,
+    ) -> serde_json::Value {
+        json!({
+            "apiVersion": api_version,
+            "kind": kind,
+            "metadata": {
+                "name": name,
+                "labels": labels("web"),
+                "ownerReferences": owner_uid.map(|uid| vec![json!({
+                    "apiVersion": "rootster.xyz/v1",
+                    "kind": "RustOperator",
+                    "name": "web",
+                    "uid": uid,
+                })]),
+            },
+        })
+    }
+
+    #[tokio::test]
+    async fn config_only_applies_no_deployment() {
+        let api = FakeApi::new();
+        let obj = operator(&api, json!({ "mode": "config-only" }));
+        api.insert(DEPLOY_PATH, deployment(None));
+
+        apply(obj, Arc::new(ctx(&api))).await.unwrap();
+
+        assert_eq!(api.calls_to("PATCH", "/deployments/"), 0);
+        // The user's own Deployment is left alone.
+        assert!(api.get(DEPLOY_PATH).is_some());
+        assert!(
+            api.get("/api/v1/namespaces/default/configmaps/web")
+                .is_some()
+        );
+        let ready = condition(&status(&api), "Ready");
+        assert_eq!(ready.reason.as_deref(), Some("ConfigOnly"));
+    }
+
+    #[tokio::test]
+    async fn config_only_prunes_a_deployment_it_created() {
+        let api = FakeApi::new();
+        let obj = operator(&api, json!({ "mode": "config-only" }));
+        api.insert(DEPLOY_PATH, deployment(Some("uid-1")));
+
+        apply(obj, Arc::new(ctx(&api))).await.unwrap();
+
+        assert!(api.get(DEPLOY_PATH).is_none());
+    }
+
+    #[tokio::test]
+    async fn orphaned_owned_child_is_pruned() {
+        let api = FakeApi::new();
+        let obj = operator(&api, json!({}));
+        let ingresses = "/apis/networking.k8s.io/v1/namespaces/default/ingresses";
+        let ingress = |name, uid| child("networking.k8s.io/v1", "Ingress", name, uid);
+        api.insert(&format!("{ingresses}/old"), ingress("old", Some("uid-1")));
+        api.insert(
+            &format!("{ingresses}/foreign"),
+            ingress("foreign", Some("uid-2")),
+        );
+        api.insert(&format!("{ingresses}/unowned"), ingress("unowned", None));
+
+        apply(obj, Arc::new(ctx(&api))).await.unwrap();
+
+        assert!(api.get(&format!("{ingresses}/old")).is_none());
+        assert!(api.get(&format!("{ingresses}/foreign")).is_some());
+        assert!(api.get(&format!("{ingresses}/unowned")).is_some());
+    }
+
+    fn api_error(code: u16, reason: &str, message: &str) -> kube::Error {
+        kube::Error::Api(kube::core::ErrorResponse {
+            status: "Failure".into(),
+            message: message.into(),
+            reason: reason.into(),
+            code,
+        })
+    }
+
+    #[tokio::test]
+    async fn throttled_reconcile_requeues_after_the_short_delay() {
+        let api = FakeApi::new();
+        let ctx = Arc::new(ctx(&api));
+        let obj = operator(&api, json!({}));
+        let err = finalizer::Error::ApplyFailed(api_error(429, "TooManyRequests", "slow down"));
+
+        let action = error_policy(obj, &err, ctx.clone());
+
+        assert_eq!(action, Action::requeue(Duration::from_secs(3)));
+        let count = |label| ctx.metrics.requeues.with_label_values(&[label]).get();
+        assert_eq!((count("throttled"), count("error")), (1, 0));
+        // Throttling is not a failure of the object, so its backoff does not grow.
+        assert!(ctx.failures.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn immutable_field_rejection_is_recognised() {
+        let err = |code, message: &str| api_error(code, "Invalid", message);
+        let immutable = err(422, "spec.selector: Invalid value: field is immutable");
+        assert_eq!(
+            immutable_field_error(&immutable),
+            Some("spec.selector: Invalid value: field is immutable")
+        );
+        assert_eq!(
+            immutable_field_error(&err(422, "spec.replicas: must be >= 0")),
+            None
+        );
+        assert_eq!(immutable_field_error(&err(500, "field is immutable")), None);
+    }
+
+    #[tokio::test]
+    async fn immutable_field_change_sets_the_condition_and_pauses() {
+        let api = FakeApi::new();
+        let obj = operator(&api, json!({}));
+        api.fail_once(
+            http::Method::PATCH,
+            DEPLOY_PATH,
+            422,
+            "Invalid",
+            "Deployment.apps \"web\" is invalid: spec.selector: Invalid value: field is immutable",
+        );
+
+        let action = apply(obj, Arc::new(ctx(&api))).await.unwrap();
+
+        assert_eq!(action, Action::await_change());
+        let st = status(&api);
+        let changed = condition(&st, "ImmutableFieldChanged");
+        assert_eq!(changed.status, "True");
+        assert_eq!(changed.reason.as_deref(), Some("RecreateRequired"));
+        assert!(changed.message.unwrap().contains("field is immutable"));
+        assert_eq!(st.up_to_date, Some(false));
+    }
+
+    #[tokio::test]
+    async fn recreate_deletes_then_reapplies() {
+        let api = FakeApi::new();
+        let obj = operator(&api, json!({ "recreate_on_immutable_change": true }));
+        api.insert(DEPLOY_PATH, deployment(Some("uid-1")));
+        api.fail_once(
+            http::Method::PATCH,
+            DEPLOY_PATH,
+            422,
+            "Invalid",
+            "spec.selector: Invalid value: field is immutable",
+        );
+
+        apply(obj, Arc::new(ctx(&api))).await.unwrap();
+
+        let deploy_calls: Vec<_> = api
+            .calls()
+            .into_iter()
+            .filter(|c| c.ends_with(DEPLOY_PATH))
+            .filter(|c| !c.starts_with("GET"))
+            .collect();
+        assert_eq!(
+            deploy_calls,
+            [
+                format!("PATCH {DEPLOY_PATH}"),
+                format!("DELETE {DEPLOY_PATH}"),
+                format!("PATCH {DEPLOY_PATH}"),
+            ]
+        );
+        assert!(api.get(DEPLOY_PATH).is_some());
+        assert_eq!(status(&api).up_to_date, Some(true));
+    }
+
+    #[tokio::test]
+    async fn allowlisted_annotation_survives_a_forced_apply() {
+        let api = FakeApi::new();
+        let obj = operator(&api, json!({ "force_apply": true }));
+        let mut live = deployment(Some("uid-1"));
+        live["metadata"]["annotations"] = json!({
+            "sidecar.istio.io/inject": "true",
+            "example.com/note": "hand edit",
+        });
+        api.insert(DEPLOY_PATH, live);
+        let mut ctx = ctx(&api);
+        ctx.preserved_annotation_prefixes = DEFAULT_PRESERVED_ANNOTATION_PREFIXES
+            .split(',')
+            .map(String::from)
+            .collect();
+
+        apply(obj, Arc::new(ctx)).await.unwrap();
+
+        let annotations = &api.get(DEPLOY_PATH).unwrap()["metadata"]["annotations"];
+        assert_eq!(annotations["sidecar.istio.io/inject"], "true");
+        assert!(annotations.get("example.com/note").is_none());
+    }
+
+    #[tokio::test]
+    async fn unmanaged_service_is_pruned_when_owned_and_not_applied() {
+        let api = FakeApi::new();
+        let obj = operator(&api, json!({ "manage_service": false }));
+        api.insert(
+            SVC_PATH,
+            child("v1", "Service", "web-service", Some("uid-1")),
+        );
+
+        apply(obj, Arc::new(ctx(&api))).await.unwrap();
+
+        assert!(api.get(SVC_PATH).is_none());
+        assert_eq!(api.calls_to("PATCH", "/services/"), 0);
+        let managed = status(&api).managed_resources.unwrap();
+        assert!(managed.iter().all(|r| r.kind != "Service"));
+    }
+
+    #[tokio::test]
+    async fn unmanaged_service_leaves_a_user_service_alone() {
+        let api = FakeApi::new();
+        let obj = operator(&api, json!({ "manage_service": false }));
+        api.insert(SVC_PATH, child("v1", "Service", "web-service", None));
+
+        apply(obj, Arc::new(ctx(&api))).await.unwrap();
+
+        assert!(api.get(SVC_PATH).is_some());
+        assert_eq!(api.calls_to("DELETE", "/services/"), 0);
+        assert_eq!(api.calls_to("PATCH", "/services/"), 0);
+    }
+
+    #[cfg(feature = "otel")]
+    #[tokio::test]
+    async fn reconcile_spans_reach_the_exporter() {
+        use opentelemetry::{KeyValue, Value};
+        use opentelemetry_sdk::trace::{InMemorySpanExporter, SdkTracerProvider};
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let exporter = InMemorySpanExporter::default();
+        let provider = SdkTracerProvider::builder()
+            .with_simple_exporter(exporter.clone())
+            .build();
+        let subscriber = tracing_subscriber::registry().with(crate::telemetry::layer(&provider));
+        let guard = tracing::subscriber::set_default(subscriber);
+        let api = FakeApi::new();
+        reconcile(operator(&api, json!({})), Arc::new(ctx(&api)))
+            .await
+            .unwrap();
+        drop(guard);
+        provider.force_flush().unwrap();
+
+        let spans = exporter.get_finished_spans().unwrap();
+        let root = spans.iter().find(|s| s.name == "reconcile").unwrap();
+        for attr in [
+            KeyValue::new("ns", "default"),
+            KeyValue::new("object", "web"),
+            KeyValue::new("generation", Value::I64(1)),
+        ] {
+            assert!(root.attributes.contains(&attr), "missing {attr:?}");
+        }
+        let children: Vec<_> = spans.iter().filter(|s| s.name == "apply_child").collect();
+        assert!(!children.is_empty());
+        let root_id = root.span_context.span_id();
+        assert!(children.iter().all(|s| s.parent_span_id == root_id));
+    }
+
+    #[tokio::test]
+    async fn invalid_service_type_degrades_without_touching_the_service() {
+        let api = FakeApi::new();
+        let obj = operator(&api, json!({ "service_type": "ExternalName" }));
+        let action = apply(obj, Arc::new(ctx(&api))).await.unwrap();
+        assert_eq!(action, Action::await_change());
+        let degraded = condition(&status(&api), "Degraded");
+        assert_eq!(degraded.status, "True");
+        assert_eq!(degraded.reason.as_deref(), Some("InvalidSpec"));
+        assert!(degraded.message.unwrap().contains("ExternalName"));
+        assert_eq!(api.calls_to("PATCH", "/services/"), 0);
+    }
+
+    #[tokio::test]
+    async fn deleted_deployment_is_recreated_on_the_next_reconcile() {
+        let api = FakeApi::new();
+        let ctx = Arc::new(ctx(&api));
+        apply(operator(&api, json!({})), ctx.clone()).await.unwrap();
+        let mut deleted: Deployment =
+            serde_json::from_value(api.get(DEPLOY_PATH).unwrap()).unwrap();
+        deleted.metadata.namespace = Some("default".into());
+        api.remove(DEPLOY_PATH);
+        api.clear_calls();
+
+        // The stored CR still carries the applied hash; the delete event must defeat the fast path.
+        assert_eq!(
+            mark_changed(&ctx.in_sync, owners_of)(deleted),
+            [ObjectRef::new("web").within("default")]
+        );
+        apply(stored(&api), ctx).await.unwrap();
+        assert_eq!(api.calls_to("PATCH", DEPLOY_PATH), 1);
+        let deploy = api.get(DEPLOY_PATH).unwrap();
+        assert_eq!(deploy["metadata"]["ownerReferences"][0]["uid"], "uid-1");
+    }
+
+    #[tokio::test]
+    async fn condition_time_is_kept_until_the_status_flips() {
+        const EARLIER: &str = "2020-01-01T00:00:00Z";
+        let api = FakeApi::new();
+        let ctx = Arc::new(ctx(&api));
+        apply(operator(&api, json!({})), ctx.clone()).await.unwrap();
+        let mut cr = api.get(CR_PATH).unwrap();
+        for c in cr["status"]["conditions"].as_array_mut().unwrap() {
+            c["last_transition_time"] = json!(EARLIER);
+        }
+        api.insert(CR_PATH, cr);
+        // `condition` drops the transition time, so read the stored list directly.
+        let degraded_since = |api: &FakeApi| {
+            let conditions = status(api).conditions.unwrap();
+            let degraded = conditions.into_iter().find(|c| c.type_ == "Degraded");
+            degraded.unwrap().last_transition_time
+        };
+
+        apply(edit_spec(&api, json!({ "replicas": 2 })), ctx.clone())
+            .await
+            .unwrap();
+        assert_eq!(degraded_since(&api).as_deref(), Some(EARLIER));
+
+        apply(edit_spec(&api, json!({ "replicas": -1 })), ctx.clone())
+            .await
+            .unwrap();
+        let flipped = degraded_since(&api).unwrap();
+        assert_ne!(flipped, EARLIER);
+
+        // Nothing changed, so the status is not patched again.
+        api.clear_calls();
+        apply(stored(&api), ctx).await.unwrap();
+        assert_eq!(api.calls_to("PATCH", "/status"), 0);
+        assert_eq!(degraded_since(&api), Some(flipped));
+    }
+
+    #[tokio::test]
+    async fn legacy_finalizer_is_migrated_to_the_current_one() {
+        const OLD: &str = "rootster.xyz/legacy";
+        const NEW: &str = "rootster.xyz/finalizer";
+        let api = FakeApi::new();
+        operator(&api, json!({}));
+        let mut cr = api.get(CR_PATH).unwrap();
+        cr["metadata"]["finalizers"] = json!([OLD, "other.io/keep"]);
+        api.insert(CR_PATH, cr);
+        let crs: Api<RustOperator> = Api::namespaced(api.client(), "default");
+        let legacy = [OLD.to_string()];
+
+        assert!(
+            migrate_finalizers(&crs, &stored(&api), &legacy, Some(NEW))
+                .await
+                .unwrap()
+        );
+        assert_eq!(stored(&api).finalizers(), ["other.io/keep", NEW]);
+        assert!(
+            !migrate_finalizers(&crs, &stored(&api), &legacy, Some(NEW))
+                .await
+                .unwrap()
+        );
+
+        // A CR already being deleted only loses the legacy name.
+        let mut cr = api.get(CR_PATH).unwrap();
+        cr["metadata"]["finalizers"] = json!([OLD, "other.io/keep"]);
+        cr["metadata"]["deletionTimestamp"] = json!("2020-01-01T00:00:00Z");
+        api.insert(CR_PATH, cr);
+        assert!(
+            migrate_finalizers(&crs, &stored(&api), &legacy, Some(NEW))
+                .await
+                .unwrap()
+        );
+        assert_eq!(stored(&api).finalizers(), ["other.io/keep"]);
+    }
+
+    #[tokio::test]
+    async fn changed_spec_updates_the_applied_hash() {
+        let api = FakeApi::new();
+        let ctx = Arc::new(ctx(&api));
+        apply(operator(&api, json!({})), ctx.clone()).await.unwrap();
+        let first = status(&api).applied_spec_hash.unwrap();
+
+        apply(edit_spec(&api, json!({ "message": "changed" })), ctx)
+            .await
+            .unwrap();
+        let second = status(&api).applied_spec_hash.unwrap();
+        assert_ne!(first, second);
+        assert_eq!(second, desired_state_hash(&stored(&api).spec));
+    }
+
+    #[tokio::test]
+    async fn sighup_reload_keeps_the_fingerprint_and_annotates_pods() {
+        let api = FakeApi::new();
+        let ctx = Arc::new(ctx(&api));
+        add_pod(&api, "web-a", "Running");
+        let template_annotations = |api: &FakeApi| {
+            api.get(DEPLOY_PATH).unwrap()["spec"]["template"]["metadata"]["annotations"].clone()
+        };
+        let pod_hash = |api: &FakeApi| {
+            api.get(&format!("{PODS_PATH}/web-a")).unwrap()["metadata"]["annotations"]
+                [CONFIG_HASH_ANNOTATION]
+                .clone()
+        };
+
+        let obj = operator(
+            &api,
+            json!({ "reload_strategy": "sighup", "html": "<p>v1</p>" }),
+        );
+        apply(obj, ctx.clone()).await.unwrap();
+        let before = template_annotations(&api);
+        let first = pod_hash(&api);
+        assert_eq!(first, json!(config_hash(&stored(&api).spec)));
+
+        let obj = edit_spec(&api, json!({ "html": "<p>v2</p>" }));
+        apply(obj, ctx).await.unwrap();
+        assert_eq!(template_annotations(&api), before);
+        assert_eq!(pod_hash(&api), json!(config_hash(&stored(&api).spec)));
+        assert_ne!(pod_hash(&api), first);
+    }
+
+    #[tokio::test]
+    async fn cr_missing_a_required_label_is_blocked() {
+        let api = FakeApi::new();
+        let ctx = Arc::new(Ctx {
+            required_labels: vec!["team".into(), "cost-center".into()],
+            propagate_required_labels: true,
+            ..ctx(&api)
+        });
+        let obj = operator(&api, json!({}));
+        assert_eq!(
+            apply(obj, ctx.clone()).await.unwrap(),
+            Action::await_change()
+        );
+        let violation = condition(&status(&api), "PolicyViolation");
+        assert_eq!(violation.status, "True");
+        assert_eq!(
+            violation.message.as_deref(),
+            Some("missing required labels: team, cost-center")
+        );
+        assert!(api.get(DEPLOY_PATH).is_none());
+
+        let mut cr = api.get(CR_PATH).unwrap();
+        cr["metadata"]["labels"] = json!({ "team": "web", "cost-center": "42" });
+        api.insert(CR_PATH, cr);
+        apply(stored(&api), ctx).await.unwrap();
+        assert_eq!(condition(&status(&api), "PolicyViolation").status, "False");
+        let labels = &api.get(DEPLOY_PATH).unwrap()["metadata"]["labels"];
+        assert_eq!(
+            (&labels["team"], &labels["cost-center"]),
+            (&json!("web"), &json!("42"))
+        );
+    }
+
+    /// Polls `cond` until it holds, failing the test after five seconds.
+    async fn eventually(cond: impl Fn() -> bool) {
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while !cond() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("condition not reached in time");
+    }
+
+    #[tokio::test]
+    async fn shutdown_signal_ends_the_controller_stream() {
+        let api = FakeApi::new();
+        operator(&api, json!({}));
+        let (stop, stopped) = tokio::sync::oneshot::channel::<()>();
+        let controller = Controller::new(Api::<RustOperator>::all(api.client()), Config::default())
+            .graceful_shutdown_on(async {
+                let _ = stopped.await;
+            })
+            .run(reconcile, error_policy, Arc::new(ctx(&api)))
+            .count();
+        let running = tokio::spawn(controller);
+
+        // Let the first reconcile land before signalling.
+        eventually(|| api.get(DEPLOY_PATH).is_some()).await;
+        stop.send(()).unwrap();
+        let reconciles = tokio::time::timeout(Duration::from_secs(5), running)
+            .await
+            .expect("controller stream ended")
+            .unwrap();
+        assert!(reconciles >= 1);
+    }
+
+    #[tokio::test]
+    async fn object_past_its_ttl_deletes_itself() {
+        let api = FakeApi::new();
+        let ctx = Arc::new(ctx(&api));
+        let ready_since = |api: &FakeApi, time: &str| {
+            let mut cr = api.get(CR_PATH).unwrap();
+            cr["status"] = json!({ "conditions": [
+                { "type": "Ready", "status": "True", "last_transition_time": time },
+            ] });
+            api.insert(CR_PATH, cr);
+            stored(api)
+        };
+
+        operator(&api, json!({ "ttl_seconds_after_ready": 3600 }));
+        let fresh = ready_since(&api, &Utc::now().to_rfc3339());
+        apply(fresh, ctx.clone()).await.unwrap();
+        assert_eq!(api.calls_to("DELETE", CR_PATH), 0);
+
+        let expired = ready_since(&api, "2020-01-01T00:00:00Z");
+        apply(expired, ctx).await.unwrap();
+        assert_eq!(api.calls_to("DELETE", CR_PATH), 1);
+        assert!(api.get(CR_PATH).is_none());
+        assert!(event_reasons(&api).contains(&"Expired".to_string()));
+    }
+
+    #[test]
+    fn finalizer_can_be_renamed_or_disabled() {
+        let custom = Some("example.com/cleanup".to_string());
+        assert_eq!(
+            resolve_finalizers(false, None, Vec::new()),
+            (Some(FINALIZER.to_string()), Vec::new())
+        );
+        assert_eq!(
+            resolve_finalizers(false, custom.clone(), Vec::new()),
+            (custom.clone(), vec![FINALIZER.to_string()])
+        );
+        assert_eq!(
+            resolve_finalizers(true, custom, Vec::new()),
+            (None, vec![FINALIZER.to_string()])
+        );
+    }
+
+    #[tokio::test]
+    async fn custom_finalizer_replaces_the_default() {
+        let api = FakeApi::new();
+        let (finalizer, legacy_finalizers) =
+            resolve_finalizers(false, Some("example.com/cleanup".into()), Vec::new());
+        let ctx = Arc::new(Ctx {
+            finalizer,
+            legacy_finalizers,
+            ..ctx(&api)
+        });
+        operator(&api, json!({}));
+        let mut cr = api.get(CR_PATH).unwrap();
+        cr["metadata"]["finalizers"] = json!([FINALIZER]);
+        api.insert(CR_PATH, cr);
+
+        reconcile(stored(&api), ctx.clone()).await.unwrap();
+        assert_eq!(stored(&api).finalizers(), ["example.com/cleanup"]);
+        reconcile(stored(&api), ctx).await.unwrap();
+        assert!(api.get(DEPLOY_PATH).is_some());
+    }
+
+    #[tokio::test]
+    async fn disabled_finalizer_still_lets_the_cr_go() {
+        let api = FakeApi::new();
+        let (finalizer, legacy_finalizers) = resolve_finalizers(true, None, Vec::new());
+        let ctx = Arc::new(Ctx {
+            finalizer,
+            legacy_finalizers,
+            ..ctx(&api)
+        });
+        reconcile(operator(&api, json!({})), ctx.clone())
+            .await
+            .unwrap();
+        assert!(stored(&api).finalizers().is_empty());
+        assert!(api.get(DEPLOY_PATH).is_some());
+
+        // An object from before the switch drops the old finalizer; children are left to the GC.
+        let mut cr = api.get(CR_PATH).unwrap();
+        cr["metadata"]["finalizers"] = json!([FINALIZER]);
+        cr["metadata"]["deletionTimestamp"] = json!("2026-01-01T00:00:00Z");
+        api.insert(CR_PATH, cr);
+        reconcile(stored(&api), ctx).await.unwrap();
+        assert!(stored(&api).finalizers().is_empty());
+        assert!(api.get(DEPLOY_PATH).is_some());
+    }
+
+    #[tokio::test]
+    async fn control_characters_are_escaped_in_the_stored_status() {
+        let api = FakeApi::new();
+        let obj = operator(&api, json!({ "message": "hi\u{1b}[31m red\nnext" }));
+        apply(obj, Arc::new(ctx(&api))).await.unwrap();
+        assert_eq!(
+            status(&api).observed_message.as_deref(),
+            Some(r"hi\u{1b}[31m red\nnext")
+        );
+    }
+
+    #[tokio::test]
+    async fn failed_ingress_still_applies_the_deployment() {
+        const INGRESS_PATH: &str = "/apis/networking.k8s.io/v1/namespaces/default/ingresses/web";
+        let api = FakeApi::new();
+        let obj = operator(&api, json!({ "ingress_host": "web.example.com" }));
+        api.fail_once(
+            http::Method::PATCH,
+            INGRESS_PATH,
+            500,
+            "InternalError",
+            "admission webhook unavailable",
+        );
+        assert!(apply(obj, Arc::new(ctx(&api))).await.is_err());
+
+        assert!(api.get(DEPLOY_PATH).is_some());
+        assert!(api.get(SVC_PATH).is_some());
+        assert!(api.get(INGRESS_PATH).is_none());
+        let status = status(&api);
+        let partial = condition(&status, "PartialFailure");
+        assert_eq!(partial.status, "True");
+        assert_eq!(partial.reason.as_deref(), Some("ApplyFailed"));
+        assert!(partial.message.unwrap().starts_with("Ingress: "));
+        assert_eq!(status.up_to_date, Some(false));
+    }
+
+    #[test]
+    fn error_backoff_doubles_up_to_the_cap() {
+        // Jitter only ever shortens the delay, by at most a fifth.
+        let within = |failures, full: Duration| {
+            let delay = error_backoff(failures);
+            assert!(
+                delay <= full && delay >= full.mul_f64(0.8),
+                "{failures}: {delay:?}"
+            );
+        };
+        within(1, BASE_ERROR_BACKOFF);
+        within(2, BASE_ERROR_BACKOFF * 2);
+        within(4, BASE_ERROR_BACKOFF * 8);
+        within(6, MAX_ERROR_BACKOFF);
+        within(u32::MAX, MAX_ERROR_BACKOFF);
+    }
+
+    #[tokio::test]
+    async fn successful_reconcile_resets_the_backoff() {
+        let api = FakeApi::new();
+        let ctx = Arc::new(ctx(&api));
+        let obj = operator(&api, json!({}));
+        let objref = ObjectRef::from_obj(&*obj);
+        let err = finalizer::Error::ApplyFailed(api_error(500, "InternalError", "boom"));
+        for _ in 0..3 {
+            error_policy(obj.clone(), &err, ctx.clone());
+        }
+        assert_eq!(ctx.failures.lock().unwrap().get(&objref), Some(&3));
+
+        reconcile(obj, ctx.clone()).await.unwrap();
+        assert!(ctx.failures.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn hung_reconcile_times_out_with_an_error() {
+        let api = FakeApi::new();
+        api.stall(DEPLOY_PATH);
+        let ctx = Arc::new(Ctx {
+            reconcile_timeout: Duration::from_millis(50),
+            ..ctx(&api)
+        });
+        let err = reconcile(operator(&api, json!({})), ctx).await.unwrap_err();
+        assert!(matches!(
+            &err,
+            finalizer::Error::ApplyFailed(kube::Error::Service(_))
+        ));
+        assert!(err.to_string().contains("timed out after 50ms"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn object_without_a_uid_is_skipped() {
+        let api = FakeApi::new();
+        let mut obj = (*operator(&api, json!({}))).clone();
+        obj.metadata.uid = None;
+        let action = apply(Arc::new(obj), Arc::new(ctx(&api))).await.unwrap();
+        assert_eq!(action, Action::await_change());
+        assert!(api.get(DEPLOY_PATH).is_none());
+    }
+
+    #[tokio::test]
+    async fn absent_ingress_is_not_deleted() {
+        const INGRESS_PATH: &str = "/apis/networking.k8s.io/v1/namespaces/default/ingresses/web";
+        let api = FakeApi::new();
+        let obj = operator(&api, json!({}));
+        apply(obj.clone(), Arc::new(ctx(&api))).await.unwrap();
+        assert_eq!(api.calls_to("DELETE", "/ingresses/"), 0);
+
+        api.insert(
+            INGRESS_PATH,
+            child("networking.k8s.io/v1", "Ingress", "web", Some("uid-1")),
+        );
+        apply(obj, Arc::new(ctx(&api))).await.unwrap();
+        assert_eq!(api.calls_to("DELETE", "/ingresses/"), 1);
+        assert!(api.get(INGRESS_PATH).is_none());
+    }
+
+    #[test]
+    fn conflict_message_names_each_manager_once() {
+        let message = "Apply failed with 2 conflicts: conflict with \"kubectl-edit\" using apps/v1: \
+            .spec.replicas, conflict with \"hpa\" using apps/v1: .spec.replicas, \
+            conflict with \"kubectl-edit\" using apps/v1: .spec.template";
+        assert_eq!(conflicting_managers(message), ["hpa", "kubectl-edit"]);
+        assert!(conflicting_managers("something else").is_empty());
+    }
+
+    #[tokio::test]
+    async fn apply_conflict_sets_the_degraded_condition() {
+        let api = FakeApi::new();
+        let obj = operator(&api, json!({ "force_apply": false }));
+        api.fail_once(
+            http::Method::PATCH,
+            DEPLOY_PATH,
+            409,
+            "Conflict",
+            "Apply failed with 1 conflict: conflict with \"kubectl-edit\" using apps/v1: .spec.replicas",
+        );
+
+        let action = apply(obj, Arc::new(ctx(&api))).await.unwrap();
+
+        assert_eq!(action, Action::await_change());
+        let st = status(&api);
+        let degraded = condition(&st, "Degraded");
+        assert_eq!(degraded.status, "True");
+        assert_eq!(degraded.reason.as_deref(), Some("FieldConflict"));
+        assert!(
+            degraded
+                .message
+                .unwrap()
+                .contains("managed by kubectl-edit")
+        );
+        assert_eq!(st.up_to_date, Some(false));
+    }
+
+    #[tokio::test]
+    async fn third_party_finalizer_survives_add_and_remove() {
+        const OTHER: &str = "other.io/cleanup";
+        let api = FakeApi::new();
+        let mut ctx = ctx(&api);
+        ctx.finalizer = Some(FINALIZER.into());
+        let ctx = Arc::new(ctx);
+        operator(&api, json!({}));
+        let mut cr = api.get(CR_PATH).unwrap();
+        cr["metadata"]["finalizers"] = json!([OTHER]);
+        api.insert(CR_PATH, cr);
+
+        reconcile(stored(&api), ctx.clone()).await.unwrap();
+        assert_eq!(stored(&api).finalizers(), [OTHER, FINALIZER]);
+
+        let mut cr = api.get(CR_PATH).unwrap();
+        cr["metadata"]["deletionTimestamp"] = json!("2026-01-01T00:00:00Z");
+        api.insert(CR_PATH, cr);
+        reconcile(stored(&api), ctx).await.unwrap();
+        assert_eq!(stored(&api).finalizers(), [OTHER]);
+    }
+
+    #[tokio::test]
+    async fn crd_presence_is_checked_before_starting() {
+        const CRD_PATH: &str =
+            "/apis/apiextensions.k8s.io/v1/customresourcedefinitions/rustoperators.rootster.xyz";
+        let api = FakeApi::new();
+        let err = ensure_crd_installed(&api.client()).await.unwrap_err();
+        assert!(err.to_string().contains("is not installed"));
+
+        api.insert(CRD_PATH, serde_json::to_value(RustOperator::crd()).unwrap());
+        ensure_crd_installed(&api.client()).await.unwrap();
+
+        // Without permission to read CRDs the check is skipped rather than fatal.
+        api.remove(CRD_PATH);
+        api.fail_once(http::Method::GET, CRD_PATH, 403, "Forbidden", "forbidden");
+        ensure_crd_installed(&api.client()).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn partial_readiness_depends_on_require_all_replicas() {
+        let ready_with = |require_all| async move {
+            let api = FakeApi::new();
+            let obj = operator(
+                &api,
+                json!({ "replicas": 3, "require_all_replicas": require_all }),
+            );
+            let mut deploy = deployment(Some("uid-1"));
+            deploy["status"] = json!({ "readyReplicas": 1 });
+            api.insert(DEPLOY_PATH, deploy);
+            apply(obj, Arc::new(ctx(&api))).await.unwrap();
+            condition(&status(&api), "Ready")
+        };
+
+        let lenient = ready_with(false).await;
+        assert_eq!(lenient.status, "True");
+        assert_eq!(lenient.reason.as_deref(), Some("PodsAvailable"));
+        assert_eq!(lenient.message.as_deref(), Some("ready_replicas=1/3"));
+
+        let strict = ready_with(true).await;
+        assert_eq!(strict.status, "False");
+        assert_eq!(strict.reason.as_deref(), Some("Scaling"));
+        assert_eq!(strict.message.as_deref(), Some("ready_replicas=1/3"));
+    }
+
+    #[test]
+    fn child_watches_select_the_webapp_label() {
+        let config = child_watch_config();
+        assert_eq!(
+            config.label_selector.as_deref(),
+            Some("app.kubernetes.io/name=webapp")
+        );
+        // Every child carries the label, so the filtered watches still see them.
+        assert_eq!(labels("web")["app.kubernetes.io/name"], "webapp");
+    }
+
+    #[tokio::test]
+    async fn zero_replicas_report_an_intentional_pause() {
+        let api = FakeApi::new();
+        let ctx = Arc::new(ctx(&api));
+        apply(operator(&api, json!({ "replicas": 0 })), ctx.clone())
+            .await
+            .unwrap();
+        let st = status(&api);
+        let paused = condition(&st, "Paused");
+        assert_eq!(paused.status, "True");
+        assert_eq!(paused.reason.as_deref(), Some("ScaledToZero"));
+        let ready = condition(&st, "Ready");
+        assert_eq!(ready.status, "False");
+        assert_eq!(ready.reason.as_deref(), Some("ScaledToZero"));
+
+        apply(edit_spec(&api, json!({ "replicas": 1 })), ctx)
+            .await
+            .unwrap();
+        let st = status(&api);
+        let paused = condition(&st, "Paused");
+        assert_eq!(paused.status, "False");
+        assert_eq!(paused.reason.as_deref(), Some("Running"));
+        assert_eq!(condition(&st, "Ready").reason.as_deref(), Some("Scaling"));
+    }
+
+    #[test]
+    fn rollout_conditions_follow_the_deployment_status() {
+        let rollout = |available, updated| {
+            let d: Deployment = serde_json::from_value(json!({
+                "spec": { "replicas": 2, "selector": {}, "template": {} },
+                "status": { "availableReplicas": available, "updatedReplicas": updated },
+            }))
+            .unwrap();
+            rollout_conditions(&d)
+        };
+
+        let [available, progressing] = rollout(0, 1);
+        assert_eq!(available.status, "False");
+        assert_eq!(available.reason.as_deref(), Some("NoReplicasAvailable"));
+        assert_eq!(progressing.status, "True");
+        assert_eq!(progressing.reason.as_deref(), Some("RollingOut"));
+        assert_eq!(progressing.message.as_deref(), Some("updated_replicas=1/2"));
+
+        let [available, progressing] = rollout(1, 2);
+        assert_eq!(available.status, "True");
+        assert_eq!(
+            available.reason.as_deref(),
+            Some("MinimumReplicasAvailable")
+        );
+        assert_eq!(progressing.status, "False");
+        assert_eq!(progressing.reason.as_deref(), Some("RolloutComplete"));
+    }
+
+    #[tokio::test]
+    async fn unowned_service_account_is_not_adopted() {
+        const SA_PATH: &str = "/api/v1/namespaces/default/serviceaccounts/default";
+        let api = FakeApi::new();
+        api.insert(SA_PATH, child("v1", "ServiceAccount", "default", None));
+        let obj = operator(
+            &api,
+            json!({ "service_account": "default", "create_service_account": true }),
+        );
+        apply(obj, Arc::new(ctx(&api))).await.unwrap();
+
+        let degraded = condition(&status(&api), "Degraded");
+        assert_eq!(degraded.status, "True");
+        assert_eq!(degraded.reason.as_deref(), Some("ServiceAccountExists"));
+        assert_eq!(api.calls_to("PATCH", SA_PATH), 0);
+        assert!(api.get(SA_PATH).unwrap()["metadata"]["ownerReferences"].is_null());
+    }
+
+    #[tokio::test]
+    async fn readiness_waits_for_the_initial_sync() {
+        const CRS: &str = "/apis/rootster.xyz/v1/rustoperators";
+        let api = FakeApi::new();
+        operator(&api, json!({}));
+        api.fail_once(
+            http::Method::GET,
+            CRS,
+            500,
+            "InternalError",
+            "etcd unavailable",
+        );
+        let controller = Controller::new(Api::<RustOperator>::all(api.client()), Config::default());
+        let ready = Arc::new(AtomicBool::new(false));
+        tokio::spawn(report_ready_on_sync(controller.store(), ready.clone()));
+        let running = tokio::spawn(
+            controller
+                .run(reconcile, error_policy, Arc::new(ctx(&api)))
+                .count(),
+        );
+
+        eventually(|| api.calls_to("GET", CRS) > 0).await;
+        assert!(
+            !ready.load(Ordering::Relaxed),
+            "ready before the list succeeded"
+        );
+        eventually(|| ready.load(Ordering::Relaxed)).await;
+        running.abort();
+    }
 }
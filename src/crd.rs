@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use kube::{CustomResource, CustomResourceExt};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -12,30 +14,368 @@ use serde::{Deserialize, Serialize};
     namespaced
 )]
 #[kube(status = "RustOperatorStatus")]
+#[kube(category = "web", category = "all")]
+#[kube(shortname = "rop")]
+#[kube(
+    printcolumn = r#"{"name":"Ready","type":"integer","jsonPath":".status.ready_replicas"}"#,
+    printcolumn = r#"{"name":"Service-Type","type":"string","jsonPath":".spec.service_type"}"#,
+    printcolumn = r#"{"name":"Message","type":"string","jsonPath":".status.observed_message","priority":1}"#,
+    printcolumn = r#"{"name":"Age","type":"date","jsonPath":".metadata.creationTimestamp"}"#
+)]
 pub struct RustOperatorSpec {
     /// Echoed into status
     pub message: String,
     /// Inline HTML -> ConfigMap index.html
     #[serde(default)]
     pub html: String,
+    /// ConfigMap data key (and mounted file name) for `html` (default "index.html")
+    #[serde(default)]
+    pub configmap_key: Option<String>,
+    /// Where the ConfigMap is mounted in the served container (default "/usr/share/nginx/html")
+    #[serde(default)]
+    pub html_mount_path: Option<String>,
+    /// Mount the ConfigMap read-only (default true). ConfigMap volumes stay read-only to the
+    /// kubelet regardless; false only drops the mount's `readOnly` flag.
+    #[serde(default)]
+    pub html_read_only: Option<bool>,
+    /// Container image repository (default "nginx")
+    #[serde(default)]
+    pub image: Option<String>,
+    /// Container image tag (default "1.27-alpine")
+    #[serde(default)]
+    pub image_tag: Option<String>,
     /// nginx replicas
     #[serde(default = "default_replicas")]
     pub replicas: i32,
+    /// Report Ready only once every desired replica is ready, not just the first
+    #[serde(default)]
+    pub require_all_replicas: bool,
+    /// "full" manages everything; "config-only" skips the Deployment
+    #[serde(default = "default_mode")]
+    pub mode: String,
+    /// Port the container listens on
+    #[serde(default = "default_port")]
+    pub container_port: i32,
+    /// Port exposed by the Service (and targeted by the Ingress)
+    #[serde(default = "default_port")]
+    pub service_port: i32,
     /// "ClusterIP" or "NodePort"
     #[serde(default = "default_service_type")]
     pub service_type: String,
+    /// Optional fixed clusterIP for a ClusterIP Service (immutable once created)
+    #[serde(default)]
+    pub cluster_ip: Option<String>,
+    /// Delete and recreate the Deployment/Service when an immutable field must change (brief downtime)
+    #[serde(default)]
+    pub recreate_on_immutable_change: bool,
+    /// Apply children with `force`, taking over fields owned by other field managers. Set false
+    /// to leave such fields alone and report the conflict instead.
+    #[serde(default = "default_true")]
+    pub force_apply: bool,
+    /// Optional Service internalTrafficPolicy: "Cluster" or "Local"
+    #[serde(default)]
+    pub internal_traffic_policy: Option<String>,
     /// Optional Ingress host. If set, an Ingress will be created.
     #[serde(default)]
     pub ingress_host: String,
+    /// More Ingress hosts, each served by its own rule (combined with `ingress_host`)
+    #[serde(default)]
+    pub ingress_hosts: Vec<String>,
     /// Optional TLS secret name for the Ingress
     #[serde(default)]
     pub tls_secret_name: String,
+    /// Share one process namespace across the pod's containers (debug sidecars)
+    #[serde(default)]
+    pub share_process_namespace: Option<bool>,
+    /// Set false to stop Kubernetes injecting env vars for every Service in the namespace
+    #[serde(default)]
+    pub enable_service_links: Option<bool>,
+    /// How content changes reach running pods: "rollout" (default) restarts them; "sighup" keeps
+    /// them and bumps a pod annotation a sidecar can watch to reload nginx
+    #[serde(default = "default_reload_strategy")]
+    pub reload_strategy: String,
+    /// How status is written: "merge" (default) or "apply" (server-side apply, own field manager)
+    #[serde(default = "default_status_patch_strategy")]
+    pub status_patch_strategy: String,
+    /// Name of the served container (DNS-1123 label, default "nginx")
+    #[serde(default)]
+    pub container_name: Option<String>,
+    /// Overrides the served container's entrypoint; empty keeps the image's
+    #[serde(default)]
+    pub command: Vec<String>,
+    /// Overrides the served container's arguments; empty keeps the image's
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Create the "<name>-service" Service. If false, any previously managed Service is deleted.
+    #[serde(default = "default_true")]
+    pub manage_service: bool,
+    /// Existing Service the Ingress routes to when `manage_service` is false
+    #[serde(default)]
+    pub ingress_backend_service: Option<String>,
+    /// Service port the Ingress routes to, by name or number (default: `service_port`)
+    #[serde(default)]
+    pub ingress_backend_port: Option<String>,
+    /// Annotations for the Ingress only, e.g. controller-specific settings
+    /// (`nginx.ingress.kubernetes.io/rewrite-target`)
+    #[serde(default)]
+    pub ingress_annotations: BTreeMap<String, String>,
+    /// IngressClass for the Ingress; unset or empty leaves it to the cluster default
+    #[serde(default)]
+    pub ingress_class_name: Option<String>,
+    /// HTTP path the Ingress rule matches (default "/")
+    #[serde(default)]
+    pub ingress_path: Option<String>,
+    /// pathType of the Ingress rule: "Prefix" (default), "Exact" or "ImplementationSpecific"
+    #[serde(default)]
+    pub ingress_path_type: Option<String>,
+    /// Name of the port probes, the Ingress and `status.url` use by default ("http", the
+    /// `container_port`/`service_port` pair, unless an `extra_ports` name is given)
+    #[serde(default)]
+    pub primary_port: Option<String>,
+    /// Extra containers run alongside the served container
+    #[serde(default)]
+    pub sidecars: Vec<SidecarSpec>,
+    /// Containers run to completion before the served container starts (e.g. to fetch content)
+    #[serde(default)]
+    pub init_containers: Vec<InitContainerSpec>,
+    /// Where the served container mounts the `content` emptyDir that init containers fill at
+    /// "/content" (default "/content"); e.g. "/usr/share/nginx/html" with `html_mount_path` moved aside
+    #[serde(default)]
+    pub init_content_mount_path: Option<String>,
+    /// Mark this CR as the controlling owner of its children. Set false to emit a plain
+    /// owner reference so another controller can claim them; GC still deletes children with the CR.
+    #[serde(default = "default_true")]
+    pub controller_owner: bool,
+    /// Raw `Volume` objects appended to the pod (escape hatch for unmodelled volume types)
+    #[serde(default)]
+    #[schemars(schema_with = "raw_object_list")]
+    pub extra_volumes: Vec<serde_json::Value>,
+    /// Raw `VolumeMount` objects appended to the served container
+    #[serde(default)]
+    #[schemars(schema_with = "raw_object_list")]
+    pub extra_volume_mounts: Vec<serde_json::Value>,
+    /// Environment variables for the served container
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    /// CPU/memory requests and limits for the served container
+    #[serde(default)]
+    pub resources: Option<ContainerResources>,
+    /// Startup probe for slow-booting containers; liveness waits until it succeeds
+    #[serde(default)]
+    pub startup_probe: Option<ProbeSpec>,
+    /// Liveness probe (default: HTTP GET "/" on the container port)
+    #[serde(default)]
+    pub liveness_probe: Option<ProbeSpec>,
+    /// Readiness probe (default: HTTP GET "/" on the container port)
+    #[serde(default)]
+    pub readiness_probe: Option<ProbeSpec>,
+    /// Labels added to every managed child (the operator's own labels win on conflicts)
+    #[serde(default)]
+    pub extra_labels: BTreeMap<String, String>,
+    /// Annotations added to every managed child (the operator's own annotations win on conflicts)
+    #[serde(default)]
+    pub extra_annotations: BTreeMap<String, String>,
+    /// Annotations for one kind of child only, keyed by kind ("ConfigMap", "Deployment",
+    /// "Service", "Ingress", "HorizontalPodAutoscaler", "PodDisruptionBudget", "ServiceAccount",
+    /// "NetworkPolicy"), e.g. `argocd.argoproj.io/sync-wave`
+    #[serde(default)]
+    pub child_annotations: BTreeMap<String, BTreeMap<String, String>>,
+    /// Annotate the Deployment with the CR generation and rollout fingerprint it was rendered from
+    #[serde(default)]
+    pub annotate_revision: bool,
+    /// Delete this CR (and with it its children) once it has been Ready for this many seconds
+    #[serde(default)]
+    pub ttl_seconds_after_ready: Option<i64>,
+    /// Extra static files served next to the HTML (file name -> content)
+    #[serde(default)]
+    pub files: BTreeMap<String, String>,
+    /// Seconds between periodic reconciles of this CR (default 30; 0 means default)
+    #[serde(default)]
+    pub requeue_seconds: Option<u64>,
+    /// Extra Deployment selector requirements, applied only when the Deployment is created
+    #[serde(default)]
+    pub selector_match_expressions: Vec<MatchExpression>,
+    /// Node labels the pods must be scheduled on
+    #[serde(default)]
+    pub node_selector: BTreeMap<String, String>,
+    /// Raw `Toleration` objects for the pods
+    #[serde(default)]
+    #[schemars(schema_with = "raw_object_list")]
+    pub tolerations: Vec<serde_json::Value>,
+    /// Raw `Affinity` object for the pods
+    #[serde(default)]
+    #[schemars(schema_with = "raw_object")]
+    pub affinity: Option<serde_json::Value>,
+    /// ServiceAccount the pods run as (e.g. for workload identity)
+    #[serde(default)]
+    pub service_account: Option<String>,
+    /// Create `service_account` (owned by this CR) instead of referencing an existing one
+    #[serde(default)]
+    pub create_service_account: bool,
+    /// Create a PodDisruptionBudget with this minAvailable, as a count ("1") or percentage ("50%")
+    #[serde(default)]
+    pub pdb_min_available: Option<String>,
+    /// Create a NetworkPolicy admitting traffic to the pods only on their container ports
+    #[serde(default)]
+    pub network_policy: bool,
+    /// Deployment update strategy (default: Kubernetes' RollingUpdate with 25% surge/unavailable)
+    #[serde(default)]
+    pub strategy: Option<StrategySpec>,
+    /// Manage a HorizontalPodAutoscaler instead of a fixed `replicas` count
+    #[serde(default)]
+    pub autoscaling: Option<AutoscalingSpec>,
+    /// Additional ports exposed by the served container and the Service
+    #[serde(default)]
+    pub extra_ports: Vec<PortSpec>,
+}
+
+/// Named port; `service_port` defaults to `container_port`.
+#[derive(Deserialize, Serialize, Clone, Debug, Default, JsonSchema, PartialEq)]
+pub struct PortSpec {
+    pub name: String,
+    pub container_port: i32,
+    #[serde(default)]
+    pub service_port: Option<i32>,
+}
+
+/// `autoscaling/v2` HPA bounds; scales on average CPU utilization of the pods' requests.
+#[derive(Deserialize, Serialize, Clone, Debug, Default, JsonSchema, PartialEq)]
+pub struct AutoscalingSpec {
+    /// Lower bound (default 1)
+    #[serde(default)]
+    pub min_replicas: Option<i32>,
+    pub max_replicas: i32,
+    /// Target average CPU utilization in percent (default 80)
+    #[serde(default)]
+    pub target_cpu_utilization: Option<i32>,
+}
+
+/// Deployment update strategy; surge/unavailable take a count ("1") or a percentage ("25%").
+#[derive(Deserialize, Serialize, Clone, Debug, Default, JsonSchema, PartialEq)]
+pub struct StrategySpec {
+    /// "RollingUpdate" or "Recreate"
+    #[serde(rename = "type")]
+    pub type_: String,
+    #[serde(default)]
+    pub max_surge: Option<String>,
+    #[serde(default)]
+    pub max_unavailable: Option<String>,
+}
+
+/// Label selector requirement, e.g. `{key: tier, operator: NotIn, values: [batch]}`.
+#[derive(Deserialize, Serialize, Clone, Debug, Default, JsonSchema, PartialEq)]
+pub struct MatchExpression {
+    pub key: String,
+    /// In, NotIn, Exists or DoesNotExist
+    pub operator: String,
+    #[serde(default)]
+    pub values: Vec<String>,
+}
+
+/// Kubernetes quantities (e.g. "100m", "128Mi"); empty values are omitted.
+#[derive(Deserialize, Serialize, Clone, Debug, Default, JsonSchema, PartialEq)]
+pub struct ContainerResources {
+    #[serde(default)]
+    pub cpu_request: Option<String>,
+    #[serde(default)]
+    pub memory_request: Option<String>,
+    #[serde(default)]
+    pub cpu_limit: Option<String>,
+    #[serde(default)]
+    pub memory_limit: Option<String>,
+}
+
+/// Probe handler plus timing. Exactly one of `http_path`, `tcp`, `exec` is used (HTTP GET on "/" if none).
+#[derive(Deserialize, Serialize, Clone, Debug, Default, JsonSchema, PartialEq)]
+pub struct ProbeSpec {
+    /// HTTP GET path
+    #[serde(default)]
+    pub http_path: Option<String>,
+    /// TCP socket check
+    #[serde(default)]
+    pub tcp: bool,
+    /// Command run inside the container
+    #[serde(default)]
+    pub exec: Vec<String>,
+    /// Port for HTTP/TCP checks (defaults to the container port)
+    #[serde(default)]
+    pub port: Option<i32>,
+    #[serde(default)]
+    pub initial_delay_seconds: Option<i32>,
+    #[serde(default)]
+    pub period_seconds: Option<i32>,
+    #[serde(default)]
+    pub failure_threshold: Option<i32>,
+}
+
+/// Structural schema for an optional free-form object.
+fn raw_object(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+    schemars::json_schema!({
+        "type": "object",
+        "nullable": true,
+        "x-kubernetes-preserve-unknown-fields": true
+    })
+}
+
+/// Structural schema for a list of free-form objects.
+fn raw_object_list(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+    schemars::json_schema!({
+        "type": "array",
+        "default": [],
+        "items": {
+            "type": "object",
+            "x-kubernetes-preserve-unknown-fields": true
+        }
+    })
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default, JsonSchema, PartialEq)]
+pub struct SidecarSpec {
+    pub name: String,
+    pub image: String,
+    #[serde(default)]
+    pub command: Vec<String>,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Render as a native sidecar (init container with restartPolicy: Always, k8s 1.28+)
+    #[serde(default)]
+    pub native: bool,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default, JsonSchema, PartialEq)]
+pub struct InitContainerSpec {
+    pub name: String,
+    pub image: String,
+    #[serde(default)]
+    pub command: Vec<String>,
+    #[serde(default)]
+    pub args: Vec<String>,
 }
 
 fn default_replicas() -> i32 {
     1
 }
 
+fn default_true() -> bool {
+    true
+}
+
+fn default_status_patch_strategy() -> String {
+    "merge".to_string()
+}
+
+fn default_port() -> i32 {
+    80
+}
+
+fn default_reload_strategy() -> String {
+    "rollout".to_string()
+}
+
+fn default_mode() -> String {
+    "full".to_string()
+}
+
 fn default_service_type() -> String {
     "ClusterIP".to_string()
 }
@@ -47,6 +387,25 @@ pub struct HwCondition {
     pub status: String,
     pub reason: Option<String>,
     pub message: Option<String>,
+    /// RFC 3339 time `status` last changed; maintained by `upsert_condition`
+    #[serde(default)]
+    pub last_transition_time: Option<String>,
+}
+
+/// Name and phase of a single managed pod.
+#[derive(Deserialize, Serialize, Clone, Debug, Default, JsonSchema, PartialEq)]
+pub struct PodStatusEntry {
+    pub name: String,
+    pub phase: String,
+}
+
+/// Kind and name of a child currently managed by the operator.
+#[derive(
+    Deserialize, Serialize, Clone, Debug, Default, JsonSchema, PartialEq, Eq, PartialOrd, Ord,
+)]
+pub struct ManagedRef {
+    pub kind: String,
+    pub name: String,
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug, Default, JsonSchema, PartialEq)]
@@ -54,14 +413,42 @@ pub struct RustOperatorStatus {
     pub observed_message: Option<String>,
     pub ready_replicas: Option<i32>,
     pub conditions: Option<Vec<HwCondition>>,
+    /// Per-pod phases, capped to keep the object small
+    pub pod_statuses: Option<Vec<PodStatusEntry>>,
+    /// True once the current generation has been fully reconciled
+    pub up_to_date: Option<bool>,
+    /// Children the operator currently manages, sorted by kind then name
+    pub managed_resources: Option<Vec<ManagedRef>>,
+    /// `metadata.generation` of the spec last fully reconciled
+    pub observed_generation: Option<i64>,
+    /// Hash of the spec last fully reconciled, before namespace defaults are merged in
+    pub applied_spec_hash: Option<String>,
+    /// Where the site is reached: the Ingress URL, or the Service's cluster DNS name
+    pub url: Option<String>,
+    /// When `ttl_seconds_after_ready` will delete the CR (RFC 3339), while it is Ready
+    pub expires_at: Option<String>,
 }
 
-/// Helper to emit the CRD without schemars `format` annotations that OLM dislikes.
-pub fn print_crd_without_formats() -> anyhow::Result<()> {
-    let crd = RustOperator::crd();
-    let mut v = serde_json::to_value(&crd)?;
+/// The CRD without schemars `format` annotations that OLM dislikes.
+pub fn crd_without_formats() -> anyhow::Result<serde_json::Value> {
+    let mut v = serde_json::to_value(RustOperator::crd())?;
     strip_format_keys(&mut v);
-    println!("{}", serde_yaml::to_string(&v)?);
+    Ok(v)
+}
+
+/// Helper to emit `crd_without_formats`. Writes to `path` (creating parent directories) when
+/// given, otherwise prints to stdout.
+pub fn print_crd_without_formats(path: Option<&std::path::Path>) -> anyhow::Result<()> {
+    let yaml = serde_yaml::to_string(&crd_without_formats()?)?;
+    match path {
+        Some(path) => {
+            if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+                std::fs::create_dir_all(dir)?;
+            }
+            std::fs::write(path, yaml)?;
+        }
+        None => println!("{yaml}"),
+    }
     Ok(())
 }
 
@@ -82,3 +469,98 @@ fn strip_format_keys(v: &mut serde_json::Value) {
         _ => {}
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The `spec` properties of the emitted CRD schema.
+    fn spec_schema() -> serde_json::Value {
+        let crd = crd_without_formats().unwrap();
+        crd["spec"]["versions"][0]["schema"]["openAPIV3Schema"]["properties"]["spec"]["properties"]
+            .clone()
+    }
+
+    /// The CRD as `print_crd_without_formats` writes it, parsed back. `test` keeps the temp
+    /// file apart from other tests running at the same time.
+    fn emitted_crd(test: &str) -> serde_yaml::Value {
+        let file = format!("rust-operator-{test}-{}.yaml", std::process::id());
+        let path = std::env::temp_dir().join(file);
+        print_crd_without_formats(Some(&path)).unwrap();
+        let yaml = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        serde_yaml::from_str(&yaml).unwrap()
+    }
+
+    #[test]
+    fn emitted_crd_lists_the_categories() {
+        let crd = emitted_crd("categories");
+        let categories: Vec<&str> = crd["spec"]["names"]["categories"]
+            .as_sequence()
+            .unwrap()
+            .iter()
+            .filter_map(|c| c.as_str())
+            .collect();
+        assert_eq!(categories, ["web", "all"]);
+    }
+
+    #[test]
+    fn emitted_crd_has_the_short_name() {
+        let crd = emitted_crd("short-names");
+        let short_names = crd["spec"]["names"]["shortNames"].as_sequence().unwrap();
+        assert_eq!(short_names, &[serde_yaml::Value::from("rop")]);
+    }
+
+    #[test]
+    fn schema_documents_the_image_defaults() {
+        let props = spec_schema();
+        let description = |field: &str| props[field]["description"].as_str().unwrap().to_string();
+        assert!(description("image").contains("default \"nginx\""));
+        assert!(description("image_tag").contains("default \"1.27-alpine\""));
+    }
+
+    #[test]
+    fn emitted_crd_has_the_printer_columns() {
+        let crd = emitted_crd("printer-columns");
+        let columns = crd["spec"]["versions"][0]["additionalPrinterColumns"]
+            .as_sequence()
+            .unwrap();
+        let column = |name: &str| {
+            columns
+                .iter()
+                .find(|c| c["name"].as_str() == Some(name))
+                .unwrap_or_else(|| panic!("no {name} column"))
+        };
+        assert_eq!(
+            column("Ready")["jsonPath"].as_str(),
+            Some(".status.ready_replicas")
+        );
+        assert_eq!(
+            column("Service-Type")["jsonPath"].as_str(),
+            Some(".spec.service_type")
+        );
+        let message = column("Message");
+        assert_eq!(
+            message["jsonPath"].as_str(),
+            Some(".status.observed_message")
+        );
+        assert_eq!(message["priority"].as_i64(), Some(1));
+        assert_eq!(column("Age")["type"].as_str(), Some("date"));
+    }
+
+    #[test]
+    fn crd_is_written_to_a_new_directory() {
+        let dir = std::env::temp_dir().join(format!("rust-operator-crd-{}", std::process::id()));
+        let path = dir.join("config").join("crd.yaml");
+        print_crd_without_formats(Some(&path)).unwrap();
+        let yaml = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let crd: serde_yaml::Value = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(crd["kind"].as_str(), Some("CustomResourceDefinition"));
+        assert_eq!(
+            crd["metadata"]["name"].as_str(),
+            Some("rustoperators.rootster.xyz")
+        );
+    }
+}
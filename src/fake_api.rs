@@ -0,0 +1,415 @@
+//! An in-memory stand-in for the API server, for tests that drive the controller through a
+//! real `kube::Client`. Objects are stored under their request path; every request is recorded.
+
+use std::{
+    collections::BTreeMap,
+    convert::Infallible,
+    sync::{Arc, Mutex},
+};
+
+use http::{Method, Request, Response, StatusCode};
+use kube::{Client, client::Body};
+use serde_json::{Value, json};
+
+#[derive(Clone, Default)]
+pub struct FakeApi {
+    objects: Arc<Mutex<BTreeMap<String, Value>>>,
+    calls: Arc<Mutex<Vec<String>>>,
+    /// `(method, path)` pairs answered once with the given error status instead.
+    failures: Arc<Mutex<Vec<(Method, String, Value)>>>,
+    /// Paths whose requests never get an answer, like a hung API server.
+    stalled: Arc<Mutex<Vec<String>>>,
+    next_version: Arc<Mutex<u64>>,
+}
+
+impl FakeApi {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A client whose requests are all answered by this fake.
+    pub fn client(&self) -> Client {
+        let api = self.clone();
+        let service = tower::service_fn(move |req: Request<Body>| {
+            let api = api.clone();
+            async move { Ok::<_, Infallible>(api.handle(req).await) }
+        });
+        Client::new(service, "default")
+    }
+
+    /// Stores `obj` at `path` (e.g. `/api/v1/namespaces/default/configmaps/web`).
+    pub fn insert(&self, path: &str, mut obj: Value) {
+        let version = self.bump();
+        let meta = &mut obj["metadata"];
+        meta["resourceVersion"] = json!(version);
+        if meta.get("uid").is_none() {
+            meta["uid"] = json!(format!("uid-{version}"));
+        }
+        self.objects.lock().unwrap().insert(path.into(), obj);
+    }
+
+    pub fn get(&self, path: &str) -> Option<Value> {
+        self.objects.lock().unwrap().get(path).cloned()
+    }
+
+    /// Drops the object at `path`, as if someone deleted it behind the controller's back.
+    pub fn remove(&self, path: &str) {
+        self.objects.lock().unwrap().remove(path);
+    }
+
+    /// The objects stored directly under `collection`.
+    pub fn items(&self, collection: &str) -> Vec<Value> {
+        self.list(collection, "")["items"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Every request so far, as `"METHOD /path"`.
+    pub fn calls(&self) -> Vec<String> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    pub fn clear_calls(&self) {
+        self.calls.lock().unwrap().clear();
+    }
+
+    /// Recorded calls with `method` whose path contains `fragment`.
+    pub fn calls_to(&self, method: &str, fragment: &str) -> usize {
+        self.calls()
+            .iter()
+            .filter(|c| c.starts_with(&format!("{method} ")) && c.contains(fragment))
+            .count()
+    }
+
+    /// Answers the next `method path` request with an error status.
+    pub fn fail_once(&self, method: Method, path: &str, code: u16, reason: &str, message: &str) {
+        self.failures
+            .lock()
+            .unwrap()
+            .push((method, path.into(), status(code, reason, message)));
+    }
+
+    /// Leaves every later request to `path` unanswered.
+    pub fn stall(&self, path: &str) {
+        self.stalled.lock().unwrap().push(path.into());
+    }
+
+    fn bump(&self) -> String {
+        let mut version = self.next_version.lock().unwrap();
+        *version += 1;
+        version.to_string()
+    }
+
+    async fn handle(&self, req: Request<Body>) -> Response<Body> {
+        let (parts, body) = req.into_parts();
+        let path = parts.uri.path().to_string();
+        let query = parts.uri.query().unwrap_or_default().to_string();
+        let body = body.collect_bytes().await.unwrap_or_default();
+        let body: Value = serde_json::from_slice(&body).unwrap_or(Value::Null);
+        self.calls
+            .lock()
+            .unwrap()
+            .push(format!("{} {path}", parts.method));
+
+        let failure = {
+            let mut failures = self.failures.lock().unwrap();
+            failures
+                .iter()
+                .position(|(m, p, _)| *m == parts.method && *p == path)
+                .map(|i| failures.remove(i).2)
+        };
+        if let Some(status) = failure {
+            let code = status["code"].as_u64().unwrap_or(500) as u16;
+            return respond(code, status);
+        }
+
+        // Watches stay open without events, like an idle cluster, and stalled paths never answer;
+        // the caller gives up on both.
+        if query_param(&query, "watch").as_deref() == Some("true")
+            || self.stalled.lock().unwrap().contains(&path)
+        {
+            return std::future::pending().await;
+        }
+
+        // Deletes carry it in their DeleteOptions body instead of the query.
+        let dry_run = query_param(&query, "dryRun").is_some() || !body["dryRun"].is_null();
+        let (base, subresource) = match path.strip_suffix("/status") {
+            Some(base) if self.get(base).is_some() => (base.to_string(), true),
+            _ => (path.clone(), false),
+        };
+        match parts.method {
+            Method::GET => match self.get(&base) {
+                Some(obj) => respond(200, obj),
+                None if is_collection(&base) => {
+                    let selector = query_param(&query, "labelSelector").unwrap_or_default();
+                    respond(200, self.list(&base, &selector))
+                }
+                None => not_found(&base),
+            },
+            Method::POST => {
+                let name = body["metadata"]["name"]
+                    .as_str()
+                    .map(String::from)
+                    .unwrap_or_else(|| {
+                        let prefix = body["metadata"]["generateName"].as_str().unwrap_or("obj-");
+                        format!("{prefix}{}", self.bump())
+                    });
+                let item = format!("{path}/{name}");
+                if self.get(&item).is_some() {
+                    return respond(
+                        409,
+                        status(409, "AlreadyExists", &format!("{name} already exists")),
+                    );
+                }
+                let mut obj = body;
+                obj["metadata"]["name"] = json!(name);
+                if !dry_run {
+                    self.insert(&item, obj);
+                }
+                respond(201, self.get(&item).unwrap_or(Value::Null))
+            }
+            Method::PUT => {
+                let Some(current) = self.get(&base) else {
+                    return not_found(&base);
+                };
+                if body["metadata"]["resourceVersion"] != current["metadata"]["resourceVersion"] {
+                    return respond(409, status(409, "Conflict", "the object has been modified"));
+                }
+                let mut obj = body;
+                obj["metadata"]["uid"] = current["metadata"]["uid"].clone();
+                if !dry_run {
+                    self.insert(&base, obj);
+                }
+                respond(200, self.get(&base).unwrap_or(Value::Null))
+            }
+            Method::PATCH => {
+                let content_type = parts
+                    .headers
+                    .get(http::header::CONTENT_TYPE)
+                    .and_then(|v| v.to_str().ok())
+                    .unwrap_or_default();
+                let current = self.get(&base);
+                let obj = if subresource {
+                    let mut obj = current.unwrap_or_default();
+                    merge(&mut obj["status"], &body["status"]);
+                    obj
+                } else if content_type.contains("apply-patch") {
+                    // Server-side apply: the applied object replaces what the manager set, while
+                    // identity and status stay as the server had them.
+                    let mut obj = body;
+                    if let Some(current) = &current {
+                        obj["metadata"]["uid"] = current["metadata"]["uid"].clone();
+                        if let Some(status) = current.get("status") {
+                            obj["status"] = status.clone();
+                        }
+                    }
+                    obj
+                } else {
+                    let Some(mut obj) = current else {
+                        return not_found(&base);
+                    };
+                    if content_type.contains("json-patch") {
+                        if let Err(message) = json_patch(&mut obj, &body) {
+                            return respond(422, status(422, "Invalid", &message));
+                        }
+                    } else {
+                        merge(&mut obj, &body);
+                    }
+                    obj
+                };
+                if dry_run {
+                    return respond(200, obj);
+                }
+                self.insert(&base, obj);
+                respond(200, self.get(&base).unwrap_or(Value::Null))
+            }
+            Method::DELETE => {
+                let removed = if dry_run {
+                    self.get(&base)
+                } else {
+                    self.objects.lock().unwrap().remove(&base)
+                };
+                match removed {
+                    Some(obj) => respond(200, obj),
+                    None => not_found(&base),
+                }
+            }
+            _ => respond(405, status(405, "MethodNotAllowed", "unsupported")),
+        }
+    }
+
+    /// The objects directly under `collection` whose labels satisfy `selector`. A collection
+    /// outside any namespace (`/api/v1/pods`) lists the objects of every namespace.
+    fn list(&self, collection: &str, selector: &str) -> Value {
+        let prefix = format!("{collection}/");
+        let (group_version, plural) = collection.rsplit_once('/').unwrap_or_default();
+        let namespaced_prefix = format!("{group_version}/namespaces/");
+        let all_namespaces = !collection.contains("/namespaces/");
+        let in_collection = |path: &str| {
+            if let Some(rest) = path.strip_prefix(&prefix) {
+                return !rest.contains('/');
+            }
+            let segments: Vec<&str> = match path.strip_prefix(&namespaced_prefix) {
+                Some(rest) if all_namespaces => rest.split('/').collect(),
+                _ => return false,
+            };
+            matches!(segments[..], [_, p, _] if p == plural)
+        };
+        let items: Vec<Value> = self
+            .objects
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(path, _)| in_collection(path))
+            .map(|(_, obj)| obj.clone())
+            .filter(|obj| matches_selector(&obj["metadata"]["labels"], selector))
+            .collect();
+        json!({
+            "apiVersion": "v1",
+            "kind": "List",
+            // Watchers refuse to start from a list without a version.
+            "metadata": { "resourceVersion": self.next_version.lock().unwrap().to_string() },
+            "items": items,
+        })
+    }
+}
+
+/// Collections end in a resource name, i.e. have an even number of segments after the
+/// group/version prefix (`/api/v1/<plural>` or `/api/v1/namespaces/<ns>/<plural>`).
+fn is_collection(path: &str) -> bool {
+    let segments: Vec<&str> = path.trim_start_matches('/').split('/').collect();
+    let rest = match segments.first() {
+        Some(&"api") => &segments[2..],
+        _ => &segments[3.min(segments.len())..],
+    };
+    rest.len() % 2 == 1
+}
+
+fn matches_selector(labels: &Value, selector: &str) -> bool {
+    selector
+        .split(',')
+        .filter(|term| !term.is_empty())
+        .all(|term| match term.split_once('=') {
+            Some((key, value)) => labels[key].as_str() == Some(value),
+            None => labels.get(term).is_some(),
+        })
+}
+
+fn query_param(query: &str, key: &str) -> Option<String> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(k, _)| *k == key)
+        .map(|(_, v)| percent_decode(v))
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap_or("");
+                out.push(u8::from_str_radix(hex, 16).unwrap_or(b'?'));
+                i += 3;
+            }
+            b'+' => {
+                out.push(b' ');
+                i += 1;
+            }
+            b => {
+                out.push(b);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// JSON patch (RFC 6902), limited to the `test`, `add`, `replace` and `remove` operations.
+fn json_patch(target: &mut Value, ops: &Value) -> Result<(), String> {
+    for op in ops.as_array().into_iter().flatten() {
+        let path = op["path"].as_str().unwrap_or_default();
+        let (parent, last) = path.rsplit_once('/').unwrap_or_default();
+        let value = op.get("value").cloned().unwrap_or(Value::Null);
+        match op["op"].as_str() {
+            Some("test") => {
+                let current = target.pointer(path).cloned().unwrap_or(Value::Null);
+                if current != value {
+                    return Err(format!("test of {path} failed"));
+                }
+            }
+            Some("add" | "replace") => match target.pointer_mut(parent) {
+                Some(Value::Array(items)) if last == "-" => items.push(value),
+                Some(Value::Array(items)) => {
+                    let i: usize = last.parse().map_err(|_| format!("bad index in {path}"))?;
+                    if op["op"] == "add" {
+                        items.insert(i, value);
+                    } else {
+                        items[i] = value;
+                    }
+                }
+                Some(Value::Object(map)) => {
+                    map.insert(last.into(), value);
+                }
+                _ => return Err(format!("no parent for {path}")),
+            },
+            Some("remove") => match target.pointer_mut(parent) {
+                Some(Value::Array(items)) => {
+                    let i: usize = last.parse().map_err(|_| format!("bad index in {path}"))?;
+                    items.remove(i);
+                }
+                Some(Value::Object(map)) => {
+                    map.remove(last);
+                }
+                _ => return Err(format!("no parent for {path}")),
+            },
+            other => return Err(format!("unsupported op {other:?}")),
+        }
+    }
+    Ok(())
+}
+
+/// JSON merge patch (RFC 7386).
+fn merge(target: &mut Value, patch: &Value) {
+    let Some(patch) = patch.as_object() else {
+        *target = patch.clone();
+        return;
+    };
+    if !target.is_object() {
+        *target = json!({});
+    }
+    let map = target.as_object_mut().unwrap();
+    for (k, v) in patch {
+        if v.is_null() {
+            map.remove(k);
+        } else {
+            merge(map.entry(k.clone()).or_insert(Value::Null), v);
+        }
+    }
+}
+
+fn status(code: u16, reason: &str, message: &str) -> Value {
+    json!({
+        "kind": "Status",
+        "apiVersion": "v1",
+        "status": "Failure",
+        "code": code,
+        "reason": reason,
+        "message": message,
+    })
+}
+
+fn not_found(path: &str) -> Response<Body> {
+    respond(404, status(404, "NotFound", &format!("{path} not found")))
+}
+
+fn respond(code: u16, body: Value) -> Response<Body> {
+    Response::builder()
+        .status(StatusCode::from_u16(code).unwrap())
+        .header(http::header::CONTENT_TYPE, "application/json")
+        .body(Body::from(serde_json::to_vec(&body).unwrap()))
+        .unwrap()
+}
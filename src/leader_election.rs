@@ -0,0 +1,262 @@
+use std::time::Duration;
+
+use k8s_openapi::{
+    api::coordination::v1::{Lease, LeaseSpec},
+    apimachinery::pkg::apis::meta::v1::MicroTime,
+    chrono::{DateTime, TimeDelta, Utc},
+};
+use kube::{
+    Api, Client,
+    api::{ObjectMeta, PostParams},
+};
+use tokio::time::{Instant, sleep};
+use tracing::{info, warn};
+
+/// Lease the operator replicas compete for, in `POD_NAMESPACE` (or the client's namespace).
+const LEASE_NAME: &str = "rust-operator";
+/// How long a Lease stays valid without renewal; a standby may take over after this.
+const LEASE_DURATION: Duration = Duration::from_secs(15);
+/// The leader steps down if it could not renew for this long, before the Lease can expire.
+const RENEW_DEADLINE: Duration = Duration::from_secs(10);
+/// How often the leader renews and standbys retry.
+const RETRY_PERIOD: Duration = Duration::from_secs(2);
+
+/// Holds or waits for the `coordination.k8s.io/v1` Lease that gates the controller loop.
+pub struct LeaderElector {
+    api: Api<Lease>,
+    identity: String,
+}
+
+/// True when nobody holds the Lease or its holder stopped renewing it.
+fn expired(spec: &LeaseSpec, now: DateTime<Utc>) -> bool {
+    let duration = TimeDelta::seconds(spec.lease_duration_seconds.unwrap_or_default().into());
+    match (spec.holder_identity.as_deref(), spec.renew_time.as_ref()) {
+        (Some(holder), Some(renewed)) if !holder.is_empty() => renewed.0 + duration < now,
+        _ => true,
+    }
+}
+
+/// The Lease was created or updated by another candidate since we read it.
+fn lost_race(err: &kube::Error) -> bool {
+    matches!(err, kube::Error::Api(resp) if resp.code == 409)
+}
+
+impl LeaderElector {
+    /// Identity is the pod name (`POD_NAME`, else `HOSTNAME`), so the holder is easy to find.
+    pub fn new(client: Client) -> Self {
+        let ns = std::env::var("POD_NAMESPACE")
+            .unwrap_or_else(|_| client.default_namespace().to_string());
+        let identity = std::env::var("POD_NAME")
+            .or_else(|_| std::env::var("HOSTNAME"))
+            .unwrap_or_else(|_| format!("rust-operator-{}", std::process::id()));
+        Self {
+            api: Api::namespaced(client, &ns),
+            identity,
+        }
+    }
+
+    fn acquired_spec(&self, now: DateTime<Utc>, transitions: i32) -> LeaseSpec {
+        LeaseSpec {
+            holder_identity: Some(self.identity.clone()),
+            lease_duration_seconds: Some(LEASE_DURATION.as_secs() as i32),
+            acquire_time: Some(MicroTime(now)),
+            renew_time: Some(MicroTime(now)),
+            lease_transitions: Some(transitions),
+            ..Default::default()
+        }
+    }
+
+    /// One election round: takes the Lease if it is missing, expired or already ours, and
+    /// renews it. Returns whether we hold it afterwards.
+    async fn try_acquire_or_renew(&self) -> kube::Result<bool> {
+        let now = Utc::now();
+        let Some(mut lease) = self.api.get_opt(LEASE_NAME).await? else {
+            let lease = Lease {
+                metadata: ObjectMeta {
+                    name: Some(LEASE_NAME.into()),
+                    ..Default::default()
+                },
+                spec: Some(self.acquired_spec(now, 0)),
+            };
+            return match self.api.create(&PostParams::default(), &lease).await {
+                Ok(_) => Ok(true),
+                Err(e) if lost_race(&e) => Ok(false),
+                Err(e) => Err(e),
+            };
+        };
+        let spec = lease.spec.get_or_insert_default();
+        if spec.holder_identity.as_deref() == Some(self.identity.as_str()) {
+            spec.renew_time = Some(MicroTime(now));
+        } else if expired(spec, now) {
+            *spec = self.acquired_spec(now, spec.lease_transitions.unwrap_or_default() + 1);
+        } else {
+            return Ok(false);
+        }
+        // The replace carries the resourceVersion we read, so only one candidate can win.
+        match self
+            .api
+            .replace(LEASE_NAME, &PostParams::default(), &lease)
+            .await
+        {
+            Ok(_) => Ok(true),
+            Err(e) if lost_race(&e) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Waits until this replica holds the Lease.
+    pub async fn acquire(&self) {
+        info!("waiting for leader lease {LEASE_NAME} as {}", self.identity);
+        loop {
+            match self.try_acquire_or_renew().await {
+                Ok(true) => {
+                    info!("acquired leader lease {LEASE_NAME}");
+                    return;
+                }
+                Ok(false) => {}
+                Err(e) => warn!("leader election failed: {e}"),
+            }
+            sleep(RETRY_PERIOD).await;
+        }
+    }
+
+    /// Keeps renewing the Lease; returns once leadership is lost, either because another
+    /// replica took it or because renewals kept failing past `RENEW_DEADLINE`.
+    pub async fn hold(&self) {
+        let mut renewed = Instant::now();
+        loop {
+            sleep(RETRY_PERIOD).await;
+            match self.try_acquire_or_renew().await {
+                Ok(true) => renewed = Instant::now(),
+                Ok(false) => {
+                    warn!("leader lease {LEASE_NAME} was taken over");
+                    return;
+                }
+                Err(e) if renewed.elapsed() >= RENEW_DEADLINE => {
+                    warn!("could not renew leader lease {LEASE_NAME}: {e}");
+                    return;
+                }
+                Err(e) => warn!("renewing leader lease {LEASE_NAME} failed: {e}"),
+            }
+        }
+    }
+
+    /// Gives the Lease up on a clean shutdown so a standby can take over without waiting for
+    /// it to expire.
+    pub async fn release(&self) {
+        let Ok(Some(mut lease)) = self.api.get_opt(LEASE_NAME).await else {
+            return;
+        };
+        let Some(spec) = lease.spec.as_mut() else {
+            return;
+        };
+        if spec.holder_identity.as_deref() != Some(self.identity.as_str()) {
+            return;
+        }
+        spec.holder_identity = None;
+        spec.renew_time = None;
+        match self
+            .api
+            .replace(LEASE_NAME, &PostParams::default(), &lease)
+            .await
+        {
+            Ok(_) => info!("released leader lease {LEASE_NAME}"),
+            Err(e) => warn!("releasing leader lease {LEASE_NAME} failed: {e}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::Method;
+    use serde_json::json;
+
+    use super::*;
+    use crate::fake_api::FakeApi;
+
+    const LEASE_PATH: &str = "/apis/coordination.k8s.io/v1/namespaces/default/leases/rust-operator";
+
+    fn elector(api: &FakeApi, identity: &str) -> LeaderElector {
+        LeaderElector {
+            api: Api::namespaced(api.client(), "default"),
+            identity: identity.into(),
+        }
+    }
+
+    fn lease(api: &FakeApi) -> LeaseSpec {
+        serde_json::from_value(api.get(LEASE_PATH).unwrap()["spec"].clone()).unwrap()
+    }
+
+    #[test]
+    fn lease_expires_once_renewals_stop() {
+        let now = Utc::now();
+        let held = |renewed: DateTime<Utc>| LeaseSpec {
+            holder_identity: Some("a".into()),
+            lease_duration_seconds: Some(15),
+            renew_time: Some(MicroTime(renewed)),
+            ..Default::default()
+        };
+        assert!(!expired(&held(now), now));
+        assert!(!expired(&held(now - TimeDelta::seconds(10)), now));
+        assert!(expired(&held(now - TimeDelta::seconds(20)), now));
+        assert!(expired(&LeaseSpec::default(), now));
+        let released = LeaseSpec {
+            holder_identity: Some(String::new()),
+            ..held(now)
+        };
+        assert!(expired(&released, now));
+    }
+
+    #[tokio::test]
+    async fn lease_is_acquired_renewed_and_taken_over() {
+        let api = FakeApi::new();
+        let (a, b) = (elector(&api, "a"), elector(&api, "b"));
+
+        assert!(a.try_acquire_or_renew().await.unwrap());
+        assert_eq!(lease(&api).holder_identity.as_deref(), Some("a"));
+        assert!(!b.try_acquire_or_renew().await.unwrap());
+        assert!(a.try_acquire_or_renew().await.unwrap());
+        assert_eq!(lease(&api).lease_transitions, Some(0));
+
+        // a stops renewing; once the lease runs out b takes it.
+        let mut stale = api.get(LEASE_PATH).unwrap();
+        stale["spec"]["renewTime"] = json!("2020-01-01T00:00:00.000000Z");
+        api.insert(LEASE_PATH, stale);
+        assert!(b.try_acquire_or_renew().await.unwrap());
+        let taken = lease(&api);
+        assert_eq!(taken.holder_identity.as_deref(), Some("b"));
+        assert_eq!(taken.lease_transitions, Some(1));
+        assert!(!a.try_acquire_or_renew().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn losing_the_update_race_is_not_an_error() {
+        let api = FakeApi::new();
+        let a = elector(&api, "a");
+        api.fail_once(
+            Method::POST,
+            "/apis/coordination.k8s.io/v1/namespaces/default/leases",
+            409,
+            "AlreadyExists",
+            "exists",
+        );
+        assert!(!a.try_acquire_or_renew().await.unwrap());
+
+        assert!(a.try_acquire_or_renew().await.unwrap());
+        api.fail_once(Method::PUT, LEASE_PATH, 409, "Conflict", "modified");
+        assert!(!a.try_acquire_or_renew().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn release_clears_only_our_own_lease() {
+        let api = FakeApi::new();
+        let (a, b) = (elector(&api, "a"), elector(&api, "b"));
+        a.try_acquire_or_renew().await.unwrap();
+
+        b.release().await;
+        assert_eq!(lease(&api).holder_identity.as_deref(), Some("a"));
+        a.release().await;
+        assert_eq!(lease(&api).holder_identity, None);
+        assert!(b.try_acquire_or_renew().await.unwrap());
+    }
+}
@@ -1,17 +1,228 @@
 mod controller;
 mod crd;
+#[cfg(test)]
+mod fake_api;
+mod leader_election;
+mod metrics;
 mod resources;
+#[cfg(feature = "otel")]
+mod telemetry;
+mod webhook;
 
-use crate::{controller::run_operator, crd::print_crd_without_formats};
+use serde::Deserialize;
+use tracing::Subscriber;
+use tracing_subscriber::{
+    EnvFilter, Layer, fmt::MakeWriter, layer::SubscriberExt, registry::LookupSpan,
+    util::SubscriberInitExt,
+};
+
+use crate::{
+    controller::run_operator,
+    crd::{RustOperator, print_crd_without_formats},
+    resources::validate_spec,
+};
+
+/// `rust-operator validate <file>...`: runs the controller's spec validation over every
+/// RustOperator document in the given YAML files without contacting a cluster.
+fn validate_manifests(paths: &[String]) -> anyhow::Result<()> {
+    anyhow::ensure!(!paths.is_empty(), "usage: rust-operator validate <file>...");
+    let mut invalid = 0;
+    for path in paths {
+        for (name, result) in validate_documents(&std::fs::read_to_string(path)?)? {
+            match result {
+                Ok(()) => println!("{path}: {name}: ok"),
+                Err(msg) => {
+                    invalid += 1;
+                    println!("{path}: {name}: invalid: {msg}");
+                }
+            }
+        }
+    }
+    anyhow::ensure!(invalid == 0, "{invalid} invalid RustOperator manifest(s)");
+    Ok(())
+}
+
+/// The validation result of each RustOperator document in `text`, by name.
+fn validate_documents(text: &str) -> anyhow::Result<Vec<(String, Result<(), String>)>> {
+    let mut results = Vec::new();
+    for doc in serde_yaml::Deserializer::from_str(text) {
+        let value = serde_yaml::Value::deserialize(doc)?;
+        if value.get("kind").and_then(|k| k.as_str()) != Some("RustOperator") {
+            continue;
+        }
+        let name = value["metadata"]["name"]
+            .as_str()
+            .unwrap_or("<unnamed>")
+            .to_string();
+        let result = serde_yaml::from_value::<RustOperator>(value)
+            .map_err(|e| e.to_string())
+            .and_then(|obj| validate_spec(&obj.spec));
+        results.push((name, result));
+    }
+    Ok(results)
+}
+
+/// Reconciles of CRs annotated with `rootster.xyz/log-level` log at that level inside their span.
+fn log_filter() -> EnvFilter {
+    EnvFilter::new("info,[reconcile{log_level=debug}]=debug,[reconcile{log_level=trace}]=trace")
+}
+
+/// Human-readable lines, or with `json` one JSON object per line, including the reconcile
+/// span's fields.
+fn fmt_layer<S, W>(json: bool, writer: W) -> Box<dyn Layer<S> + Send + Sync>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    let layer = tracing_subscriber::fmt::layer().with_writer(writer);
+    if json {
+        layer.json().boxed()
+    } else {
+        layer.boxed()
+    }
+}
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    tracing_subscriber::fmt().with_env_filter("info").init();
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("validate") {
+        return validate_manifests(&args[1..]);
+    }
 
-    if std::env::var("PRINT_CRD").is_ok() {
-        print_crd_without_formats()?;
+    let json = std::env::var("LOG_FORMAT").is_ok_and(|f| f.trim().eq_ignore_ascii_case("json"));
+    let subscriber = tracing_subscriber::registry()
+        .with(log_filter())
+        .with(fmt_layer(json, std::io::stdout));
+    #[cfg(feature = "otel")]
+    let provider = telemetry::otlp_provider()?;
+    #[cfg(feature = "otel")]
+    let subscriber = subscriber.with(provider.as_ref().map(telemetry::layer));
+    subscriber.init();
+
+    // `PRINT_CRD=1` (or `true`, or empty) prints to stdout; any other value is a file path.
+    if let Ok(target) = std::env::var("PRINT_CRD") {
+        let path =
+            (!matches!(target.trim(), "" | "1" | "true")).then(|| std::path::Path::new(&target));
+        print_crd_without_formats(path)?;
         return Ok(());
     }
 
-    run_operator().await
+    let result = run_operator().await;
+    #[cfg(feature = "otel")]
+    if let Some(provider) = provider {
+        // Flush spans still buffered in the batch exporter.
+        let _ = provider.shutdown();
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use tracing::{debug, info_span};
+
+    use super::*;
+
+    /// Runs `f` under the operator's filter and returns the formatted log lines.
+    fn captured(f: impl FnOnce()) -> String {
+        let buf = Arc::new(Mutex::new(Vec::new()));
+        let writer = buf.clone();
+        let subscriber = tracing_subscriber::registry().with(log_filter()).with(
+            tracing_subscriber::fmt::layer()
+                .with_ansi(false)
+                .with_writer(move || Writer(writer.clone())),
+        );
+        tracing::subscriber::with_default(subscriber, f);
+        String::from_utf8(buf.lock().unwrap().clone()).unwrap()
+    }
+
+    struct Writer(Arc<Mutex<Vec<u8>>>);
+
+    impl std::io::Write for Writer {
+        fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(bytes);
+            Ok(bytes.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn log_level_annotation_raises_the_level_inside_the_span() {
+        let logs = captured(|| {
+            info_span!("reconcile", log_level = %"debug").in_scope(|| debug!("annotated"));
+            info_span!("reconcile", log_level = %"").in_scope(|| debug!("plain"));
+            debug!("outside");
+        });
+        assert!(logs.contains("annotated"));
+        assert!(!logs.contains("plain"));
+        assert!(!logs.contains("outside"));
+    }
+
+    #[test]
+    fn bad_service_type_fails_validation_with_a_clear_message() {
+        let manifest = r#"
+apiVersion: rootster.xyz/v1
+kind: RustOperator
+metadata:
+  name: good
+spec:
+  message: hello
+---
+apiVersion: v1
+kind: ConfigMap
+metadata:
+  name: ignored
+---
+apiVersion: rootster.xyz/v1
+kind: RustOperator
+metadata:
+  name: bad
+spec:
+  message: hello
+  service_type: ExternalName
+"#;
+        let results = validate_documents(manifest).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0], ("good".to_string(), Ok(())));
+        let (name, result) = &results[1];
+        assert_eq!(name, "bad");
+        let msg = result.as_ref().unwrap_err();
+        assert!(msg.contains("service_type \"ExternalName\""), "{msg}");
+        assert!(msg.contains("\"ClusterIP\", \"NodePort\" or \"LoadBalancer\""));
+
+        let path = std::env::temp_dir().join("rust-operator-validate-test.yaml");
+        std::fs::write(&path, manifest).unwrap();
+        let err = validate_manifests(&[path.display().to_string()]).unwrap_err();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(err.to_string(), "1 invalid RustOperator manifest(s)");
+    }
+
+    #[test]
+    fn both_log_formats_carry_the_reconcile_span_fields() {
+        let logged = |json| {
+            let buf = Arc::new(Mutex::new(Vec::new()));
+            let writer = buf.clone();
+            let subscriber = tracing_subscriber::registry()
+                .with(log_filter())
+                .with(fmt_layer(json, move || Writer(writer.clone())));
+            tracing::subscriber::with_default(subscriber, || {
+                info_span!("reconcile", object = %"web", ns = %"default")
+                    .in_scope(|| tracing::info!("reconciled"));
+            });
+            String::from_utf8(buf.lock().unwrap().clone()).unwrap()
+        };
+
+        let text = logged(false);
+        assert!(text.contains("reconciled"));
+        assert!(text.contains("web"));
+
+        let line: serde_json::Value = serde_json::from_str(logged(true).trim()).unwrap();
+        assert_eq!(line["fields"]["message"], "reconciled");
+        assert_eq!(line["span"]["object"], "web");
+        assert_eq!(line["span"]["ns"], "default");
+    }
 }
@@ -0,0 +1,232 @@
+use std::{
+    net::SocketAddr,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use axum::{
+    Router,
+    extract::State,
+    http::{StatusCode, header},
+    response::IntoResponse,
+    routing::get,
+};
+use prometheus::{
+    Encoder, Histogram, HistogramOpts, IntCounter, IntCounterVec, IntGauge, Opts, Registry,
+    TextEncoder,
+};
+use tracing::info;
+
+/// Prometheus collectors shared by the controller.
+#[derive(Clone)]
+pub struct Metrics {
+    pub registry: Registry,
+    /// Reconciles started.
+    pub reconciles: IntCounter,
+    /// Reconciles that returned an error.
+    pub reconcile_errors: IntCounter,
+    /// Wall-clock time of each reconcile.
+    pub reconcile_duration: Histogram,
+    /// Requeue decisions, labelled by reason.
+    pub requeues: IntCounterVec,
+    /// Reconciles currently running; approximates the backlog the controller is working through.
+    pub in_flight: IntGauge,
+}
+
+/// Decrements `in_flight` when the reconcile finishes or its future is dropped.
+pub struct InFlightGuard(IntGauge);
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.0.dec();
+    }
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        let registry = Registry::new();
+        let reconciles = IntCounter::new("reconcile_total", "Reconciles started")
+            .expect("valid reconcile metric");
+        let reconcile_errors = IntCounter::new(
+            "reconcile_errors_total",
+            "Reconciles that returned an error",
+        )
+        .expect("valid reconcile error metric");
+        let reconcile_duration = Histogram::with_opts(HistogramOpts::new(
+            "reconcile_duration_seconds",
+            "Time spent in a single reconcile",
+        ))
+        .expect("valid reconcile duration metric");
+        let requeues = IntCounterVec::new(
+            Opts::new(
+                "rust_operator_requeues_total",
+                "Reconcile outcomes by requeue reason",
+            ),
+            &["reason"],
+        )
+        .expect("valid requeue metric");
+        let in_flight = IntGauge::new(
+            "rust_operator_reconciles_in_flight",
+            "Reconciles currently in progress",
+        )
+        .expect("valid in-flight metric");
+
+        registry
+            .register(Box::new(reconciles.clone()))
+            .expect("register reconcile_total");
+        registry
+            .register(Box::new(reconcile_errors.clone()))
+            .expect("register reconcile_errors_total");
+        registry
+            .register(Box::new(reconcile_duration.clone()))
+            .expect("register reconcile_duration_seconds");
+        registry
+            .register(Box::new(requeues.clone()))
+            .expect("register requeues");
+        registry
+            .register(Box::new(in_flight.clone()))
+            .expect("register in-flight");
+
+        Self {
+            registry,
+            reconciles,
+            reconcile_errors,
+            reconcile_duration,
+            requeues,
+            in_flight,
+        }
+    }
+
+    pub fn track_in_flight(&self) -> InFlightGuard {
+        self.in_flight.inc();
+        InFlightGuard(self.in_flight.clone())
+    }
+
+    /// Renders every registered collector in the Prometheus text format.
+    pub fn render(&self) -> String {
+        let mut buf = Vec::new();
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buf)
+            .expect("encode metrics");
+        String::from_utf8(buf).expect("metrics are utf-8")
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Bind address for `/metrics`, from `METRICS_ADDR` (default `0.0.0.0:8080`, empty disables).
+pub fn metrics_addr() -> anyhow::Result<Option<SocketAddr>> {
+    let addr = std::env::var("METRICS_ADDR").unwrap_or_else(|_| "0.0.0.0:8080".into());
+    if addr.trim().is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(addr.parse()?))
+}
+
+#[derive(Clone)]
+struct ServerState {
+    metrics: Metrics,
+    ready: Arc<AtomicBool>,
+}
+
+async fn metrics_handler(State(state): State<ServerState>) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        state.metrics.render(),
+    )
+}
+
+/// 200 once `ready` is set, i.e. the controller's cache has finished its initial sync.
+async fn readyz_handler(State(state): State<ServerState>) -> impl IntoResponse {
+    if state.ready.load(Ordering::Relaxed) {
+        (StatusCode::OK, "ok")
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, "cache not synced")
+    }
+}
+
+fn router(metrics: Metrics, ready: Arc<AtomicBool>) -> Router {
+    Router::new()
+        .route("/metrics", get(metrics_handler))
+        .route("/healthz", get(|| async { "ok" }))
+        .route("/readyz", get(readyz_handler))
+        .with_state(ServerState { metrics, ready })
+}
+
+/// Serves `/metrics`, `/healthz` and `/readyz` until the task is aborted.
+pub async fn serve(
+    addr: SocketAddr,
+    metrics: Metrics,
+    ready: Arc<AtomicBool>,
+) -> anyhow::Result<()> {
+    let app = router(metrics, ready);
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    info!("serving metrics on {addr}");
+    axum::serve(listener, app).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    async fn status_of(app: &Router, path: &str) -> StatusCode {
+        let req = axum::http::Request::get(path).body(Body::empty()).unwrap();
+        app.clone().oneshot(req).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn probes_report_liveness_and_readiness() {
+        let ready = Arc::new(AtomicBool::new(false));
+        let app = router(Metrics::new(), ready.clone());
+        assert_eq!(status_of(&app, "/healthz").await, StatusCode::OK);
+        assert_eq!(
+            status_of(&app, "/readyz").await,
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+
+        ready.store(true, Ordering::Relaxed);
+        assert_eq!(status_of(&app, "/healthz").await, StatusCode::OK);
+        assert_eq!(status_of(&app, "/readyz").await, StatusCode::OK);
+        assert_eq!(status_of(&app, "/metrics").await, StatusCode::OK);
+    }
+
+    #[test]
+    fn render_exports_the_reconcile_metrics() {
+        let metrics = Metrics::new();
+        metrics.reconciles.inc();
+        metrics.reconciles.inc();
+        metrics.reconcile_errors.inc();
+        metrics.reconcile_duration.observe(0.25);
+        let text = metrics.render();
+        assert!(text.contains("reconcile_total 2"));
+        assert!(text.contains("reconcile_errors_total 1"));
+        assert!(text.contains("reconcile_duration_seconds_count 1"));
+    }
+
+    #[test]
+    fn in_flight_gauge_tracks_running_reconciles() {
+        let metrics = Metrics::new();
+        let first = metrics.track_in_flight();
+        let second = metrics.track_in_flight();
+        assert_eq!(metrics.in_flight.get(), 2);
+        drop(first);
+        assert_eq!(metrics.in_flight.get(), 1);
+        drop(second);
+        assert_eq!(metrics.in_flight.get(), 0);
+        assert!(
+            metrics
+                .render()
+                .contains("rust_operator_reconciles_in_flight 0")
+        );
+    }
+}
@@ -2,25 +2,677 @@ use std::collections::BTreeMap;
 
 use k8s_openapi::{
     api::{
-        apps::v1::Deployment,
+        apps::v1::{Deployment, DeploymentStrategy, RollingUpdateDeployment},
+        autoscaling::v2::{
+            CrossVersionObjectReference, HorizontalPodAutoscaler, HorizontalPodAutoscalerSpec,
+            MetricSpec, MetricTarget, ResourceMetricSource,
+        },
         core::v1::{
-            ConfigMap, Container, ContainerPort, PodSpec, PodTemplateSpec, Service, ServicePort,
-            ServiceSpec, Volume, VolumeMount,
+            Affinity, ConfigMap, Container, ContainerPort, DownwardAPIVolumeFile,
+            DownwardAPIVolumeSource, EnvVar, ExecAction, HTTPGetAction, KeyToPath,
+            ObjectFieldSelector, Pod, PodSpec, PodTemplateSpec, Probe, ResourceRequirements,
+            Secret, Service, ServiceAccount, ServicePort, ServiceSpec, TCPSocketAction, Toleration,
+            Volume, VolumeMount,
         },
         networking::v1::{
             HTTPIngressPath, HTTPIngressRuleValue, Ingress, IngressBackend, IngressRule,
-            IngressServiceBackend, IngressSpec, IngressTLS, ServiceBackendPort,
+            IngressServiceBackend, IngressSpec, IngressTLS, NetworkPolicy,
+            NetworkPolicyIngressRule, NetworkPolicyPort, NetworkPolicySpec, ServiceBackendPort,
         },
+        policy::v1::{PodDisruptionBudget, PodDisruptionBudgetSpec},
+    },
+    apimachinery::pkg::{
+        api::resource::Quantity,
+        apis::meta::v1::{LabelSelectorRequirement, ObjectMeta},
+        util::intstr::IntOrString,
     },
-    apimachinery::pkg::{apis::meta::v1::ObjectMeta, util::intstr::IntOrString},
+    chrono::{DateTime, TimeDelta, Utc},
 };
+use kube::{Resource, ResourceExt};
 use serde::Serialize;
 use sha2::{Digest, Sha256};
 
-use crate::crd::RustOperatorSpec;
+use crate::crd::{
+    AutoscalingSpec, ContainerResources, InitContainerSpec, ManagedRef, PodStatusEntry, PortSpec,
+    ProbeSpec, RustOperatorSpec, SidecarSpec, StrategySpec,
+};
 
 pub const FINALIZER: &str = "rustoperators.rootster.xyz/finalizer";
 
+/// Records the owning CR's UID so children can be traced to a specific CR instance.
+pub const OWNER_UID_ANNOTATION: &str = "rootster.xyz/owner-uid";
+
+/// Hash of the spec last fully applied, stored on the CR to enable the reconcile fast-path.
+pub const LAST_APPLIED_HASH_ANNOTATION: &str = "rootster.xyz/last-applied-hash";
+
+/// Pod template annotation carrying the rollout fingerprint; a change rolls the pods.
+pub const ROLLOUT_HASH_ANNOTATION: &str = "rootster.xyz/rollout-hash";
+/// CR generation the Deployment was rendered from, with `annotate_revision`.
+pub const REVISION_ANNOTATION: &str = "rootster.xyz/revision";
+
+/// Hash of the served content, set on running pods when `reload_strategy` is "sighup".
+pub const CONFIG_HASH_ANNOTATION: &str = "rootster.xyz/config-hash";
+/// Where sidecars find the pod's annotations (downward API) under the "sighup" strategy.
+pub const PODINFO_MOUNT_PATH: &str = "/etc/podinfo";
+
+/// Name of the `container_port`/`service_port` pair in the rendered port lists.
+pub const PRIMARY_PORT_NAME: &str = "http";
+/// Upper bound on the characters of `spec.message` echoed into status.
+pub const MAX_MESSAGE_CHARS: usize = 1024;
+/// Upper bound on entries reported in `status.pod_statuses`.
+pub const MAX_POD_STATUSES: usize = 20;
+/// Caps on list-shaped spec fields so one CR cannot render an unreasonable amount of objects.
+pub const MAX_FILES: usize = 64;
+pub const MAX_SIDECARS: usize = 8;
+pub const MAX_INIT_CONTAINERS: usize = 8;
+pub const MAX_PORTS: usize = 16;
+pub const MAX_INGRESS_HOSTS: usize = 16;
+/// ConfigMaps (like every object) are limited to 1 MiB by etcd.
+pub const MAX_CONFIGMAP_BYTES: usize = 1024 * 1024;
+
+pub const DEFAULT_CONTAINER_NAME: &str = "nginx";
+
+pub const DEFAULT_IMAGE: &str = "nginx";
+pub const DEFAULT_IMAGE_TAG: &str = "1.27-alpine";
+
+/// `image:tag` for the served container, falling back to the defaults for unset parts.
+pub fn container_image(spec: &RustOperatorSpec) -> String {
+    let image = spec
+        .image
+        .as_deref()
+        .filter(|i| !i.trim().is_empty())
+        .unwrap_or(DEFAULT_IMAGE);
+    let tag = spec
+        .image_tag
+        .as_deref()
+        .filter(|t| !t.trim().is_empty())
+        .unwrap_or(DEFAULT_IMAGE_TAG);
+    format!("{image}:{tag}")
+}
+
+/// ConfigMap in a CR's namespace whose data supplies defaults for unset spec fields.
+pub const NAMESPACE_DEFAULTS_CONFIGMAP: &str = "rust-operator-defaults";
+
+/// Fills unset `image`, `image_tag`, `resources` and `node_selector` entries from the namespace
+/// defaults ConfigMap data. Values set on the CR always win.
+pub fn apply_namespace_defaults(spec: &mut RustOperatorSpec, defaults: &BTreeMap<String, String>) {
+    let fill = |field: &mut Option<String>, key: &str| {
+        if field.as_deref().is_none_or(|v| v.trim().is_empty())
+            && let Some(v) = defaults.get(key).filter(|v| !v.trim().is_empty())
+        {
+            *field = Some(v.clone());
+        }
+    };
+    fill(&mut spec.image, "image");
+    fill(&mut spec.image_tag, "image_tag");
+    let mut res = spec.resources.take().unwrap_or_default();
+    fill(&mut res.cpu_request, "cpu_request");
+    fill(&mut res.memory_request, "memory_request");
+    fill(&mut res.cpu_limit, "cpu_limit");
+    fill(&mut res.memory_limit, "memory_limit");
+    spec.resources = (res != ContainerResources::default()).then_some(res);
+    // "disktype=ssd,zone=a"
+    for pair in defaults
+        .get("node_selector")
+        .into_iter()
+        .flat_map(|s| s.split(','))
+    {
+        if let Some((k, v)) = pair.split_once('=') {
+            spec.node_selector
+                .entry(k.trim().to_string())
+                .or_insert_with(|| v.trim().to_string());
+        }
+    }
+}
+
+pub const DEFAULT_CONFIGMAP_KEY: &str = "index.html";
+
+pub fn configmap_key(spec: &RustOperatorSpec) -> &str {
+    spec.configmap_key
+        .as_deref()
+        .filter(|k| !k.trim().is_empty())
+        .unwrap_or(DEFAULT_CONFIGMAP_KEY)
+}
+
+const HTML_VOLUME: &str = "html";
+pub const DEFAULT_HTML_MOUNT_PATH: &str = "/usr/share/nginx/html";
+
+/// Writable emptyDir shared by the init containers (at `CONTENT_MOUNT_PATH`) and the served
+/// container (at `init_content_mount_path`), present only when there are init containers.
+const CONTENT_VOLUME: &str = "content";
+pub const CONTENT_MOUNT_PATH: &str = "/content";
+
+pub fn init_content_mount_path(spec: &RustOperatorSpec) -> &str {
+    spec.init_content_mount_path
+        .as_deref()
+        .filter(|p| !p.trim().is_empty())
+        .unwrap_or(CONTENT_MOUNT_PATH)
+}
+
+pub fn html_mount_path(spec: &RustOperatorSpec) -> &str {
+    spec.html_mount_path
+        .as_deref()
+        .filter(|p| !p.trim().is_empty())
+        .unwrap_or(DEFAULT_HTML_MOUNT_PATH)
+}
+const PODINFO_VOLUME: &str = "podinfo";
+
+pub fn reloads_by_sighup(spec: &RustOperatorSpec) -> bool {
+    spec.reload_strategy == "sighup"
+}
+
+pub fn container_name(spec: &RustOperatorSpec) -> &str {
+    spec.container_name
+        .as_deref()
+        .filter(|n| !n.trim().is_empty())
+        .unwrap_or(DEFAULT_CONTAINER_NAME)
+}
+
+/// RFC 1123 label: lowercase alphanumerics and '-', alphanumeric at both ends, max 63 chars.
+pub fn is_dns1123_label(s: &str) -> bool {
+    let bytes = s.as_bytes();
+    !bytes.is_empty()
+        && bytes.len() <= 63
+        && bytes
+            .iter()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || *b == b'-')
+        && bytes[0] != b'-'
+        && bytes[bytes.len() - 1] != b'-'
+}
+
+/// DNS-1123 subdomain, optionally with a leading `*.` wildcard as Ingress hosts allow.
+fn is_valid_host(host: &str) -> bool {
+    is_dns1123_subdomain(host.strip_prefix("*.").unwrap_or(host))
+}
+
+fn is_dns1123_subdomain(s: &str) -> bool {
+    s.len() <= 253 && s.split('.').all(is_dns1123_label)
+}
+
+/// Name of the ServiceAccount the pods run as, if `service_account` is set.
+pub fn service_account_name(spec: &RustOperatorSpec) -> Option<&str> {
+    spec.service_account
+        .as_deref()
+        .filter(|n| !n.trim().is_empty())
+}
+
+/// Checks the spec before anything is applied; the error is surfaced on the `Degraded` condition.
+pub fn validate_spec(spec: &RustOperatorSpec) -> Result<(), String> {
+    // "." and ".." are rejected by the API server and would escape the mount as a KeyToPath path.
+    let valid_key = |key: &str| {
+        !key.is_empty()
+            && !matches!(key, "." | "..")
+            && key.len() <= 253
+            && key
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.'))
+    };
+    for (field, len, max) in [
+        ("files", spec.files.len(), MAX_FILES),
+        ("sidecars", spec.sidecars.len(), MAX_SIDECARS),
+        (
+            "init_containers",
+            spec.init_containers.len(),
+            MAX_INIT_CONTAINERS,
+        ),
+        ("extra_ports", spec.extra_ports.len(), MAX_PORTS),
+        ("ingress_hosts", spec.ingress_hosts.len(), MAX_INGRESS_HOSTS),
+    ] {
+        if len > max {
+            return Err(format!(
+                "{field} has {len} entries; at most {max} are allowed"
+            ));
+        }
+    }
+    if spec.ttl_seconds_after_ready.is_some_and(|t| t < 0) {
+        return Err("ttl_seconds_after_ready must not be negative".into());
+    }
+    if spec.replicas < 0 {
+        return Err(format!(
+            "replicas must not be negative (got {})",
+            spec.replicas
+        ));
+    }
+    if let Some(host) = ingress_hosts(spec).into_iter().find(|h| !is_valid_host(h)) {
+        return Err(format!("ingress host {host:?} is not a valid DNS name"));
+    }
+    let data_bytes = spec.html.len()
+        + spec
+            .files
+            .iter()
+            .map(|(k, v)| k.len() + v.len())
+            .sum::<usize>();
+    if data_bytes > MAX_CONFIGMAP_BYTES {
+        return Err(format!(
+            "html and files total {data_bytes} bytes; a ConfigMap holds at most {MAX_CONFIGMAP_BYTES}"
+        ));
+    }
+    let key = configmap_key(spec);
+    if !valid_key(key) {
+        return Err(format!(
+            "configmap_key {key:?} may only contain alphanumerics, '-', '_' and '.', and may not be '.' or '..'"
+        ));
+    }
+    if let Some(file) = spec.files.keys().find(|f| !valid_key(f)) {
+        return Err(format!(
+            "files key {file:?} may only contain alphanumerics, '-', '_' and '.', and may not be '.' or '..'"
+        ));
+    }
+    validate_ports(spec)?;
+    if let Some(name) = spec
+        .primary_port
+        .as_deref()
+        .filter(|n| !n.trim().is_empty())
+        && !ports(spec).iter().any(|p| p.name == name)
+    {
+        return Err(format!("primary_port {name:?} does not name any port"));
+    }
+    if let Some(kind) = spec.child_annotations.keys().find(|k| {
+        !matches!(
+            k.as_str(),
+            "ConfigMap"
+                | "Deployment"
+                | "Service"
+                | "Ingress"
+                | "HorizontalPodAutoscaler"
+                | "PodDisruptionBudget"
+                | "ServiceAccount"
+                | "NetworkPolicy"
+        )
+    }) {
+        return Err(format!(
+            "child_annotations key {kind:?} must be ConfigMap, Deployment, Service, Ingress, HorizontalPodAutoscaler, PodDisruptionBudget, ServiceAccount or NetworkPolicy"
+        ));
+    }
+    if !matches!(
+        spec.service_type.as_str(),
+        "ClusterIP" | "NodePort" | "LoadBalancer"
+    ) {
+        return Err(format!(
+            "service_type {:?} must be \"ClusterIP\", \"NodePort\" or \"LoadBalancer\"",
+            spec.service_type
+        ));
+    }
+    if let Some(ip) = &spec.cluster_ip {
+        if ip.eq_ignore_ascii_case("none") {
+            return Err(
+                "cluster_ip \"None\" (headless) is not supported; use an IP address".into(),
+            );
+        }
+        if ip.parse::<std::net::IpAddr>().is_err() {
+            return Err(format!("cluster_ip {ip:?} is not a valid IP address"));
+        }
+    }
+    if let Some(p) = &spec.internal_traffic_policy
+        && !matches!(p.as_str(), "Cluster" | "Local")
+    {
+        return Err(format!(
+            "internal_traffic_policy {p:?} must be \"Cluster\" or \"Local\""
+        ));
+    }
+    if !matches!(spec.status_patch_strategy.as_str(), "merge" | "apply") {
+        return Err(format!(
+            "status_patch_strategy {:?} must be \"merge\" or \"apply\"",
+            spec.status_patch_strategy
+        ));
+    }
+    if !matches!(spec.reload_strategy.as_str(), "rollout" | "sighup") {
+        return Err(format!(
+            "reload_strategy {:?} must be \"rollout\" or \"sighup\"",
+            spec.reload_strategy
+        ));
+    }
+    if !matches!(spec.mode.as_str(), "full" | "config-only") {
+        return Err(format!(
+            "mode {:?} must be \"full\" or \"config-only\"",
+            spec.mode
+        ));
+    }
+    let name = container_name(spec);
+    if !is_dns1123_label(name) {
+        return Err(format!("container_name {name:?} must be a DNS-1123 label"));
+    }
+    if !spec.manage_service
+        && !ingress_hosts(spec).is_empty()
+        && spec
+            .ingress_backend_service
+            .as_deref()
+            .is_none_or(|s| s.trim().is_empty())
+    {
+        return Err(
+            "ingress_backend_service is required when manage_service is false and ingress_host is set"
+                .into(),
+        );
+    }
+    if !html_mount_path(spec).starts_with('/') {
+        return Err(format!(
+            "html_mount_path {:?} must be an absolute path",
+            html_mount_path(spec)
+        ));
+    }
+    if !ingress_path(spec).starts_with('/') {
+        return Err(format!(
+            "ingress_path {:?} must start with '/'",
+            ingress_path(spec)
+        ));
+    }
+    if !matches!(
+        ingress_path_type(spec),
+        "Prefix" | "Exact" | "ImplementationSpecific"
+    ) {
+        return Err(format!(
+            "ingress_path_type {:?} must be \"Prefix\", \"Exact\" or \"ImplementationSpecific\"",
+            ingress_path_type(spec)
+        ));
+    }
+    if let Some(port) = ingress_backend_port(spec)
+        && spec.manage_service
+    {
+        let exists = ports(spec).iter().any(|p| match &port {
+            ServiceBackendPort {
+                number: Some(n), ..
+            } => p.service_port.unwrap_or(p.container_port) == *n,
+            ServiceBackendPort { name, .. } => name.as_deref() == Some(p.name.as_str()),
+        });
+        if !exists {
+            return Err(format!(
+                "ingress_backend_port {:?} does not match any Service port",
+                spec.ingress_backend_port.as_deref().unwrap_or_default()
+            ));
+        }
+    }
+    let mut seen = vec![name];
+    for sc in &spec.sidecars {
+        if !is_dns1123_label(&sc.name) {
+            return Err(format!(
+                "sidecar name {:?} must be a DNS-1123 label",
+                sc.name
+            ));
+        }
+        if seen.contains(&sc.name.as_str()) {
+            return Err(format!(
+                "container name {:?} is used more than once",
+                sc.name
+            ));
+        }
+        if sc.image.trim().is_empty() {
+            return Err(format!("sidecar {:?} needs an image", sc.name));
+        }
+        seen.push(&sc.name);
+    }
+    if !spec.init_containers.is_empty() {
+        let content = init_content_mount_path(spec);
+        if !content.starts_with('/') {
+            return Err(format!(
+                "init_content_mount_path {content:?} must be an absolute path"
+            ));
+        }
+        if content == html_mount_path(spec) {
+            return Err(format!(
+                "init_content_mount_path {content:?} is already the html_mount_path; move one of them"
+            ));
+        }
+    }
+    for ic in &spec.init_containers {
+        if !is_dns1123_label(&ic.name) {
+            return Err(format!(
+                "init container name {:?} must be a DNS-1123 label",
+                ic.name
+            ));
+        }
+        if seen.contains(&ic.name.as_str()) {
+            return Err(format!(
+                "container name {:?} is used more than once",
+                ic.name
+            ));
+        }
+        if ic.image.trim().is_empty() {
+            return Err(format!("init container {:?} needs an image", ic.name));
+        }
+        seen.push(&ic.name);
+    }
+    extra_volumes(spec)?;
+    scheduling(spec)?;
+    for e in &spec.selector_match_expressions {
+        let needs_values = match e.operator.as_str() {
+            "In" | "NotIn" => true,
+            "Exists" | "DoesNotExist" => false,
+            op => {
+                return Err(format!(
+                    "selector_match_expressions operator {op:?} must be In, NotIn, Exists or DoesNotExist"
+                ));
+            }
+        };
+        if e.key.trim().is_empty() || needs_values == e.values.is_empty() {
+            return Err(format!(
+                "selector_match_expressions entry for {:?}: {} needs a key{}",
+                e.key,
+                e.operator,
+                if needs_values {
+                    " and values"
+                } else {
+                    " and no values"
+                }
+            ));
+        }
+    }
+    if let Some(sa) = service_account_name(spec)
+        && !is_dns1123_subdomain(sa)
+    {
+        return Err(format!(
+            "service_account {sa:?} must be a DNS-1123 subdomain"
+        ));
+    }
+    if spec.create_service_account && service_account_name(spec).is_none() {
+        return Err("create_service_account needs service_account to name the account".into());
+    }
+    if let Some(m) = &spec.pdb_min_available
+        && !is_count_or_percent(m)
+    {
+        return Err(format!(
+            "pdb_min_available {m:?} must be a count or a percentage"
+        ));
+    }
+    if let Some(s) = &spec.strategy {
+        match s.type_.as_str() {
+            "RollingUpdate" => {
+                for (field, v) in [
+                    ("max_surge", &s.max_surge),
+                    ("max_unavailable", &s.max_unavailable),
+                ] {
+                    if !v.as_deref().is_none_or(is_count_or_percent) {
+                        return Err(format!(
+                            "strategy.{field} {:?} must be a count or a percentage",
+                            v.as_deref().unwrap_or_default()
+                        ));
+                    }
+                }
+            }
+            "Recreate" => {
+                if s.max_surge.is_some() || s.max_unavailable.is_some() {
+                    return Err(
+                        "strategy Recreate does not take max_surge or max_unavailable".into(),
+                    );
+                }
+            }
+            other => {
+                return Err(format!(
+                    "strategy.type {other:?} must be \"RollingUpdate\" or \"Recreate\""
+                ));
+            }
+        }
+    }
+    if let Some(a) = &spec.autoscaling {
+        let min = a.min_replicas.unwrap_or(1);
+        if min < 1 || a.max_replicas < min {
+            return Err(format!(
+                "autoscaling needs 1 <= min_replicas ({min}) <= max_replicas ({})",
+                a.max_replicas
+            ));
+        }
+        if a.target_cpu_utilization.is_some_and(|t| t < 1) {
+            return Err("autoscaling.target_cpu_utilization must be at least 1".into());
+        }
+    }
+    for (field, probe) in [
+        ("startup_probe", &spec.startup_probe),
+        ("liveness_probe", &spec.liveness_probe),
+        ("readiness_probe", &spec.readiness_probe),
+    ] {
+        if let Some(p) = probe {
+            validate_probe(field, p)?;
+        }
+    }
+    Ok(())
+}
+
+/// Rejects out-of-range numbers and duplicate names or numbers across the primary and extra ports.
+fn validate_ports(spec: &RustOperatorSpec) -> Result<(), String> {
+    let ports = ports(spec);
+    for (i, p) in ports.iter().enumerate() {
+        for (field, port) in [
+            ("container_port", p.container_port),
+            ("service_port", p.service_port.unwrap_or(p.container_port)),
+        ] {
+            if !(1..=65535).contains(&port) {
+                return Err(format!(
+                    "port {:?} {field} {port} must be between 1 and 65535",
+                    p.name
+                ));
+            }
+        }
+        // Container port names are IANA service names: DNS-1123 label of at most 15 chars.
+        if p.name.len() > 15 || !is_dns1123_label(&p.name) {
+            return Err(format!(
+                "port name {:?} must be a DNS-1123 label of at most 15 characters",
+                p.name
+            ));
+        }
+        let earlier = &ports[..i];
+        if earlier.iter().any(|q| q.name == p.name) {
+            return Err(format!("port name {:?} is used more than once", p.name));
+        }
+        if earlier.iter().any(|q| q.container_port == p.container_port) {
+            return Err(format!(
+                "container_port {} is used more than once",
+                p.container_port
+            ));
+        }
+        let svc = |q: &PortSpec| q.service_port.unwrap_or(q.container_port);
+        if earlier.iter().any(|q| svc(q) == svc(p)) {
+            return Err(format!("service_port {} is used more than once", svc(p)));
+        }
+    }
+    Ok(())
+}
+
+/// The primary `http` port followed by `extra_ports`.
+pub fn ports(spec: &RustOperatorSpec) -> Vec<PortSpec> {
+    let mut ports = vec![PortSpec {
+        name: PRIMARY_PORT_NAME.into(),
+        container_port: spec.container_port,
+        service_port: Some(spec.service_port),
+    }];
+    ports.extend(spec.extra_ports.iter().cloned());
+    ports
+}
+
+/// The port probes, the Ingress and `status.url` default to: the `primary_port` entry of
+/// `ports`, or the `container_port`/`service_port` pair when unset.
+pub fn primary_port(spec: &RustOperatorSpec) -> PortSpec {
+    let name = spec
+        .primary_port
+        .as_deref()
+        .filter(|n| !n.trim().is_empty())
+        .unwrap_or(PRIMARY_PORT_NAME);
+    let mut all = ports(spec);
+    match all.iter().position(|p| p.name == name) {
+        Some(i) => all.swap_remove(i),
+        None => all.swap_remove(0),
+    }
+}
+
+/// Where the site is reached: the Ingress host (https with a TLS secret) or the managed
+/// Service's cluster DNS name on the primary port.
+pub fn site_url(spec: &RustOperatorSpec, name: &str, ns: &str) -> Option<String> {
+    if let Some(host) = ingress_hosts(spec).first() {
+        let scheme = if spec.tls_secret_name.is_empty() {
+            "http"
+        } else {
+            "https"
+        };
+        return Some(format!("{scheme}://{host}{}", ingress_path(spec)));
+    }
+    spec.manage_service.then(|| {
+        let p = primary_port(spec);
+        format!(
+            "http://{name}-service.{ns}.svc:{}",
+            p.service_port.unwrap_or(p.container_port)
+        )
+    })
+}
+
+fn validate_probe(field: &str, p: &ProbeSpec) -> Result<(), String> {
+    let handlers = [p.http_path.is_some(), p.tcp, !p.exec.is_empty()];
+    if handlers.iter().filter(|h| **h).count() > 1 {
+        return Err(format!("{field} must set only one of http_path, tcp, exec"));
+    }
+    Ok(())
+}
+
+/// Decodes `tolerations`/`affinity` into their typed forms.
+pub fn scheduling(spec: &RustOperatorSpec) -> Result<(Vec<Toleration>, Option<Affinity>), String> {
+    let tolerations = spec
+        .tolerations
+        .iter()
+        .map(|t| serde_json::from_value::<Toleration>(t.clone()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("tolerations: {e}"))?;
+    let affinity = spec
+        .affinity
+        .clone()
+        .map(serde_json::from_value::<Affinity>)
+        .transpose()
+        .map_err(|e| format!("affinity: {e}"))?;
+    Ok((tolerations, affinity))
+}
+
+/// Decodes `extra_volumes`/`extra_volume_mounts` and rejects clashes with the html volume.
+pub fn extra_volumes(spec: &RustOperatorSpec) -> Result<(Vec<Volume>, Vec<VolumeMount>), String> {
+    let volumes = spec
+        .extra_volumes
+        .iter()
+        .map(|v| serde_json::from_value::<Volume>(v.clone()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("extra_volumes: {e}"))?;
+    let mounts = spec
+        .extra_volume_mounts
+        .iter()
+        .map(|v| serde_json::from_value::<VolumeMount>(v.clone()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("extra_volume_mounts: {e}"))?;
+
+    let mut names = vec![HTML_VOLUME, PODINFO_VOLUME, CONTENT_VOLUME];
+    for v in &volumes {
+        if names.contains(&v.name.as_str()) {
+            return Err(format!("extra volume name {:?} is already in use", v.name));
+        }
+        names.push(&v.name);
+    }
+    for m in &mounts {
+        if !names.contains(&m.name.as_str()) {
+            return Err(format!(
+                "extra volume mount references unknown volume {:?}",
+                m.name
+            ));
+        }
+    }
+    Ok((volumes, mounts))
+}
+
+/// Selector matching the label `labels` puts on every child, whatever the instance.
+pub const CHILD_SELECTOR: &str = "app.kubernetes.io/name=webapp";
+
 pub fn labels(name: &str) -> BTreeMap<String, String> {
     BTreeMap::from([
         ("app.kubernetes.io/name".into(), "webapp".into()),
@@ -28,10 +680,46 @@ pub fn labels(name: &str) -> BTreeMap<String, String> {
     ])
 }
 
+fn owner_annotations(
+    owner: &k8s_openapi::apimachinery::pkg::apis::meta::v1::OwnerReference,
+) -> BTreeMap<String, String> {
+    BTreeMap::from([(OWNER_UID_ANNOTATION.to_string(), owner.uid.clone())])
+}
+
+/// Copies annotations from the live object whose keys start with one of `prefixes` into the
+/// desired metadata, unless the operator sets that key itself.
+pub fn preserve_annotations(desired: &mut ObjectMeta, current: &ObjectMeta, prefixes: &[String]) {
+    let Some(live) = &current.annotations else {
+        return;
+    };
+    let annotations = desired.annotations.get_or_insert_with(BTreeMap::new);
+    for (k, v) in live {
+        if prefixes.iter().any(|p| k.starts_with(p.as_str())) && !annotations.contains_key(k) {
+            annotations.insert(k.clone(), v.clone());
+        }
+    }
+}
+
+/// True when `meta` carries an owner reference pointing at the CR with this UID.
+pub fn is_owned_by(meta: &ObjectMeta, uid: &str) -> bool {
+    meta.owner_references.iter().flatten().any(|o| o.uid == uid)
+}
+
+/// Renders labels as a `k=v,k=v` selector string for list/watch calls.
+pub fn label_selector(labels: &BTreeMap<String, String>) -> String {
+    labels
+        .iter()
+        .map(|(k, v)| format!("{k}={v}"))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
 pub fn desired_configmap(
     name: &str,
     labels: &BTreeMap<String, String>,
     html: &str,
+    key: &str,
+    files: &BTreeMap<String, String>,
     owner: k8s_openapi::apimachinery::pkg::apis::meta::v1::OwnerReference,
 ) -> ConfigMap {
     let content = if html.trim().is_empty() {
@@ -44,10 +732,18 @@ pub fn desired_configmap(
         metadata: ObjectMeta {
             name: Some(name.to_string()),
             labels: Some(labels.clone()),
+            annotations: Some(owner_annotations(&owner)),
             owner_references: Some(vec![owner]),
             ..Default::default()
         },
-        data: Some(BTreeMap::from([("index.html".into(), content)])),
+        // `html` wins if `files` also names the HTML key.
+        data: Some(
+            files
+                .clone()
+                .into_iter()
+                .chain([(key.to_string(), content)])
+                .collect(),
+        ),
         ..Default::default()
     }
 }
@@ -55,6 +751,41 @@ pub fn desired_configmap(
 #[derive(Serialize)]
 struct RolloutInputs<'a> {
     html: &'a str,
+    // Skipped when unset or default so specs without an override keep their existing fingerprint.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    files: &'a BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    image: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    resources: Option<&'a ContainerResources>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    env: &'a BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    command: &'a [String],
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    args: &'a [String],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    html_mount_path: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    html_read_only: Option<bool>,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    init_containers: &'a [InitContainerSpec],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    init_content_mount_path: Option<&'a str>,
+}
+
+/// Hash of everything the desired children are rendered from.
+pub fn desired_state_hash(spec: &RustOperatorSpec) -> String {
+    let mut h = Sha256::new();
+    h.update(serde_json::to_vec(spec).expect("spec serialize"));
+    format!("{:x}", h.finalize())
+}
+
+/// Hash of the ConfigMap content, compared against `CONFIG_HASH_ANNOTATION` on running pods.
+pub fn config_hash(spec: &RustOperatorSpec) -> String {
+    let mut h = Sha256::new();
+    h.update(serde_json::to_vec(&(&spec.html, &spec.files)).expect("config serialize"));
+    format!("{:x}", h.finalize())
 }
 
 fn rollout_fingerprint(inp: &RolloutInputs) -> String {
@@ -64,61 +795,312 @@ fn rollout_fingerprint(inp: &RolloutInputs) -> String {
     format!("{:x}", h.finalize())
 }
 
+/// `None` when no request or limit is set, so the container keeps no `resources` block.
+pub fn desired_resources(res: Option<&ContainerResources>) -> Option<ResourceRequirements> {
+    let res = res?;
+    let quantities = |pairs: [(&str, &Option<String>); 2]| {
+        let map: BTreeMap<String, Quantity> = pairs
+            .into_iter()
+            .filter_map(|(k, v)| {
+                v.as_deref()
+                    .filter(|v| !v.trim().is_empty())
+                    .map(|v| (k.to_string(), Quantity(v.to_string())))
+            })
+            .collect();
+        (!map.is_empty()).then_some(map)
+    };
+    let requests = quantities([("cpu", &res.cpu_request), ("memory", &res.memory_request)]);
+    let limits = quantities([("cpu", &res.cpu_limit), ("memory", &res.memory_limit)]);
+    if requests.is_none() && limits.is_none() {
+        return None;
+    }
+    Some(ResourceRequirements {
+        requests,
+        limits,
+        ..Default::default()
+    })
+}
+
+pub fn desired_probe(p: &ProbeSpec, default_port: i32) -> Probe {
+    let port = IntOrString::Int(p.port.unwrap_or(default_port));
+    let mut probe = Probe {
+        initial_delay_seconds: p.initial_delay_seconds,
+        period_seconds: p.period_seconds,
+        failure_threshold: p.failure_threshold,
+        ..Default::default()
+    };
+    if !p.exec.is_empty() {
+        probe.exec = Some(ExecAction {
+            command: Some(p.exec.clone()),
+        });
+    } else if p.tcp {
+        probe.tcp_socket = Some(TCPSocketAction {
+            port,
+            ..Default::default()
+        });
+    } else {
+        probe.http_get = Some(HTTPGetAction {
+            path: Some(p.http_path.clone().unwrap_or_else(|| "/".into())),
+            port,
+            ..Default::default()
+        });
+    }
+    probe
+}
+
+/// `p` (or an HTTP GET on "/" when unset) with the given timing filled in where not specified.
+fn probe_with_defaults(p: Option<&ProbeSpec>, initial_delay: i32, period: i32) -> ProbeSpec {
+    let mut p = p.cloned().unwrap_or_default();
+    p.initial_delay_seconds.get_or_insert(initial_delay);
+    p.period_seconds.get_or_insert(period);
+    p
+}
+
+fn sidecar_container(sc: &SidecarSpec) -> Container {
+    Container {
+        name: sc.name.clone(),
+        image: Some(sc.image.clone()),
+        command: (!sc.command.is_empty()).then(|| sc.command.clone()),
+        args: (!sc.args.is_empty()).then(|| sc.args.clone()),
+        restart_policy: sc.native.then(|| "Always".into()),
+        ..Default::default()
+    }
+}
+
+fn init_container(ic: &InitContainerSpec, mounts: &[VolumeMount]) -> Container {
+    Container {
+        name: ic.name.clone(),
+        image: Some(ic.image.clone()),
+        command: (!ic.command.is_empty()).then(|| ic.command.clone()),
+        args: (!ic.args.is_empty()).then(|| ic.args.clone()),
+        volume_mounts: Some(mounts.to_vec()),
+        ..Default::default()
+    }
+}
+
+/// Renders the Deployment. Every map-valued field (labels, annotations, env, selectors) must be
+/// built from a `BTreeMap` so that rendering the same spec twice is byte-identical; a `HashMap`
+/// here would reorder the pod template and trigger a rollout on every reconcile.
 pub fn desired_deployment(
     name: &str,
     labels: &BTreeMap<String, String>,
-    replicas: i32,
+    replicas: Option<i32>,
+    revision: Option<i64>,
     owner: k8s_openapi::apimachinery::pkg::apis::meta::v1::OwnerReference,
     spec: &RustOperatorSpec,
 ) -> Deployment {
-    let fp = rollout_fingerprint(&RolloutInputs { html: &spec.html });
+    let image = container_image(spec);
+    // Under "sighup" the kubelet refreshes the mounted ConfigMap in place, so content changes
+    // must not roll the pods.
+    let sighup = reloads_by_sighup(spec);
+    let no_files = BTreeMap::new();
+    let fp = rollout_fingerprint(&RolloutInputs {
+        html: if sighup { "" } else { &spec.html },
+        files: if sighup { &no_files } else { &spec.files },
+        // Resolved, so spelling out the default image doesn't roll the pods.
+        image: Some(image.as_str())
+            .filter(|i| *i != format!("{DEFAULT_IMAGE}:{DEFAULT_IMAGE_TAG}")),
+        // An all-empty block renders no `resources`, so it must not roll the pods either.
+        resources: spec
+            .resources
+            .as_ref()
+            .filter(|r| desired_resources(Some(r)).is_some()),
+        env: &spec.env,
+        command: &spec.command,
+        args: &spec.args,
+        // Resolved, so spelling out the default doesn't roll the pods.
+        html_mount_path: Some(html_mount_path(spec)).filter(|p| *p != DEFAULT_HTML_MOUNT_PATH),
+        // Only a writable mount differs from the default.
+        html_read_only: spec.html_read_only.filter(|read_only| !read_only),
+        init_containers: &spec.init_containers,
+        // The content mount only exists alongside init containers, and then resolved like
+        // `html_mount_path`.
+        init_content_mount_path: Some(init_content_mount_path(spec))
+            .filter(|p| !spec.init_containers.is_empty() && *p != CONTENT_MOUNT_PATH),
+    });
+
+    let primary = primary_port(spec);
+    let (extra_vols, extra_mounts) = extra_volumes(spec).unwrap_or_default();
+    let (tolerations, affinity) = scheduling(spec).unwrap_or_default();
+    let mut mounts = vec![VolumeMount {
+        name: HTML_VOLUME.into(),
+        mount_path: html_mount_path(spec).into(),
+        read_only: Some(spec.html_read_only.unwrap_or(true)),
+        ..Default::default()
+    }];
+    mounts.extend(extra_mounts);
+    // Init containers see the served container's mounts plus the writable `content` volume,
+    // which is what they fill; the ConfigMap mount is read-only.
+    let has_init = !spec.init_containers.is_empty();
+    let content_mount = |path: &str| VolumeMount {
+        name: CONTENT_VOLUME.into(),
+        mount_path: path.into(),
+        ..Default::default()
+    };
+    let mut init_mounts = mounts.clone();
+    init_mounts.push(content_mount(CONTENT_MOUNT_PATH));
+    let mut init_containers: Vec<Container> = spec
+        .init_containers
+        .iter()
+        .map(|ic| init_container(ic, &init_mounts))
+        .collect();
+    if has_init {
+        mounts.push(content_mount(init_content_mount_path(spec)));
+    }
+    let mut volumes = vec![Volume {
+        name: HTML_VOLUME.into(),
+        config_map: Some(k8s_openapi::api::core::v1::ConfigMapVolumeSource {
+            name: name.to_string(),
+            items: Some(
+                spec.files
+                    .keys()
+                    .map(String::as_str)
+                    .filter(|f| *f != configmap_key(spec))
+                    .chain([configmap_key(spec)])
+                    .map(|k| KeyToPath {
+                        key: k.into(),
+                        path: k.into(),
+                        ..Default::default()
+                    })
+                    .collect(),
+            ),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }];
+    volumes.extend(extra_vols);
+    if has_init {
+        volumes.push(Volume {
+            name: CONTENT_VOLUME.into(),
+            empty_dir: Some(Default::default()),
+            ..Default::default()
+        });
+    }
+    if sighup {
+        volumes.push(Volume {
+            name: PODINFO_VOLUME.into(),
+            downward_api: Some(DownwardAPIVolumeSource {
+                items: Some(vec![DownwardAPIVolumeFile {
+                    path: "annotations".into(),
+                    field_ref: Some(ObjectFieldSelector {
+                        field_path: "metadata.annotations".into(),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+    }
+
+    let mut containers = vec![Container {
+        name: container_name(spec).into(),
+        image: Some(image),
+        command: (!spec.command.is_empty()).then(|| spec.command.clone()),
+        args: (!spec.args.is_empty()).then(|| spec.args.clone()),
+        ports: Some(
+            ports(spec)
+                .into_iter()
+                .map(|p| ContainerPort {
+                    name: Some(p.name),
+                    container_port: p.container_port,
+                    ..Default::default()
+                })
+                .collect(),
+        ),
+        // BTreeMap iteration keeps the EnvVar order stable across reconciles.
+        env: (!spec.env.is_empty()).then(|| {
+            spec.env
+                .iter()
+                .map(|(k, v)| EnvVar {
+                    name: k.clone(),
+                    value: Some(v.clone()),
+                    ..Default::default()
+                })
+                .collect()
+        }),
+        volume_mounts: Some(mounts),
+        resources: desired_resources(spec.resources.as_ref()),
+        startup_probe: spec
+            .startup_probe
+            .as_ref()
+            .map(|p| desired_probe(p, primary.container_port)),
+        liveness_probe: Some(desired_probe(
+            &probe_with_defaults(spec.liveness_probe.as_ref(), 10, 10),
+            primary.container_port,
+        )),
+        readiness_probe: Some(desired_probe(
+            &probe_with_defaults(spec.readiness_probe.as_ref(), 2, 5),
+            primary.container_port,
+        )),
+        ..Default::default()
+    }];
+    for sc in &spec.sidecars {
+        let mut container = sidecar_container(sc);
+        if sighup {
+            container.volume_mounts = Some(vec![VolumeMount {
+                name: PODINFO_VOLUME.into(),
+                mount_path: PODINFO_MOUNT_PATH.into(),
+                read_only: Some(true),
+                ..Default::default()
+            }]);
+        }
+        if sc.native {
+            init_containers.push(container);
+        } else {
+            containers.push(container);
+        }
+    }
+
+    let mut annotations = owner_annotations(&owner);
+    // Lets users map a CR edit to the Deployment (and its ReplicaSet) it produced.
+    if let Some(revision) = revision {
+        annotations.insert(REVISION_ANNOTATION.into(), revision.to_string());
+        annotations.insert(ROLLOUT_HASH_ANNOTATION.into(), fp.clone());
+    }
 
     Deployment {
         metadata: ObjectMeta {
             name: Some(name.to_string()),
             labels: Some(labels.clone()),
+            annotations: Some(annotations),
             owner_references: Some(vec![owner]),
             ..Default::default()
         },
         spec: Some(k8s_openapi::api::apps::v1::DeploymentSpec {
-            replicas: Some(replicas),
+            replicas,
+            strategy: spec.strategy.as_ref().map(desired_strategy),
             selector: k8s_openapi::apimachinery::pkg::apis::meta::v1::LabelSelector {
                 match_labels: Some(labels.clone()),
-                ..Default::default()
+                match_expressions: (!spec.selector_match_expressions.is_empty()).then(|| {
+                    spec.selector_match_expressions
+                        .iter()
+                        .map(|e| LabelSelectorRequirement {
+                            key: e.key.clone(),
+                            operator: e.operator.clone(),
+                            values: (!e.values.is_empty()).then(|| e.values.clone()),
+                        })
+                        .collect()
+                }),
             },
             template: PodTemplateSpec {
                 metadata: Some(ObjectMeta {
                     labels: Some(labels.clone()),
-                    annotations: Some(BTreeMap::from([(
-                        "rootster.xyz/rollout-hash".to_string(),
-                        fp,
-                    )])),
+                    annotations: Some(BTreeMap::from([(ROLLOUT_HASH_ANNOTATION.to_string(), fp)])),
                     ..Default::default()
                 }),
                 spec: Some(PodSpec {
-                    containers: vec![Container {
-                        name: "nginx".into(),
-                        image: Some("nginx:latest".into()),
-                        ports: Some(vec![ContainerPort {
-                            container_port: 80,
-                            ..Default::default()
-                        }]),
-                        volume_mounts: Some(vec![VolumeMount {
-                            name: "html".into(),
-                            mount_path: "/usr/share/nginx/html".into(),
-                            read_only: Some(true),
-                            ..Default::default()
-                        }]),
-                        ..Default::default()
-                    }],
-                    volumes: Some(vec![Volume {
-                        name: "html".into(),
-                        config_map: Some(k8s_openapi::api::core::v1::ConfigMapVolumeSource {
-                            name: name.to_string(),
-                            ..Default::default()
-                        }),
-                        ..Default::default()
-                    }]),
+                    containers,
+                    init_containers: (!init_containers.is_empty()).then_some(init_containers),
+                    volumes: Some(volumes),
+                    share_process_namespace: spec.share_process_namespace,
+                    enable_service_links: spec.enable_service_links,
+                    node_selector: (!spec.node_selector.is_empty())
+                        .then(|| spec.node_selector.clone()),
+                    tolerations: (!tolerations.is_empty()).then_some(tolerations),
+                    service_account_name: service_account_name(spec).map(String::from),
+                    affinity,
                     ..Default::default()
                 }),
             },
@@ -133,63 +1115,164 @@ pub fn desired_service(
     labels: &BTreeMap<String, String>,
     svc_type: &str,
     owner: k8s_openapi::apimachinery::pkg::apis::meta::v1::OwnerReference,
+    spec: &RustOperatorSpec,
 ) -> Service {
     Service {
         metadata: ObjectMeta {
             name: Some(format!("{name}-service")),
             labels: Some(labels.clone()),
+            annotations: Some(owner_annotations(&owner)),
             owner_references: Some(vec![owner]),
             ..Default::default()
         },
         spec: Some(ServiceSpec {
             selector: Some(labels.clone()),
-            ports: Some(vec![ServicePort {
-                port: 80,
-                target_port: Some(IntOrString::Int(80)),
-                ..Default::default()
-            }]),
+            ports: Some(
+                ports(spec)
+                    .into_iter()
+                    .map(|p| ServicePort {
+                        port: p.service_port.unwrap_or(p.container_port),
+                        target_port: Some(IntOrString::Int(p.container_port)),
+                        name: Some(p.name),
+                        ..Default::default()
+                    })
+                    .collect(),
+            ),
             type_: Some(svc_type.to_string()),
+            internal_traffic_policy: spec.internal_traffic_policy.clone(),
+            cluster_ip: spec.cluster_ip.clone().filter(|_| svc_type == "ClusterIP"),
             ..Default::default()
         }),
         ..Default::default()
     }
 }
 
+/// The selector is immutable, so keep the live Deployment's match expressions. Returns true when
+/// the spec asks for different ones (which are then ignored).
+pub fn pin_selector_expressions(desired: &mut Deployment, live: &Deployment) -> bool {
+    let live_exprs = live
+        .spec
+        .as_ref()
+        .and_then(|s| s.selector.match_expressions.clone())
+        .filter(|e| !e.is_empty());
+    let Some(spec) = desired.spec.as_mut() else {
+        return false;
+    };
+    let changed = spec.selector.match_expressions != live_exprs;
+    spec.selector.match_expressions = live_exprs;
+    changed
+}
+
+/// True when every Service selector entry is present on the Deployment's pod template labels.
+pub fn service_selects_pods(svc: &Service, deploy: &Deployment) -> bool {
+    let selector = svc.spec.as_ref().and_then(|s| s.selector.as_ref());
+    let pod_labels = deploy
+        .spec
+        .as_ref()
+        .and_then(|s| s.template.metadata.as_ref())
+        .and_then(|m| m.labels.as_ref());
+    match (selector, pod_labels) {
+        (Some(sel), Some(pod_labels)) => {
+            !sel.is_empty() && sel.iter().all(|(k, v)| pod_labels.get(k) == Some(v))
+        }
+        _ => false,
+    }
+}
+
+/// `ingress_host` followed by `ingress_hosts`, skipping blanks and repeats. The Ingress exists
+/// while this is non-empty.
+pub fn ingress_hosts(spec: &RustOperatorSpec) -> Vec<&str> {
+    let mut hosts: Vec<&str> = Vec::new();
+    for host in std::iter::once(&spec.ingress_host).chain(&spec.ingress_hosts) {
+        if !host.trim().is_empty() && !hosts.contains(&host.as_str()) {
+            hosts.push(host);
+        }
+    }
+    hosts
+}
+
+fn ingress_path(spec: &RustOperatorSpec) -> &str {
+    spec.ingress_path
+        .as_deref()
+        .filter(|p| !p.trim().is_empty())
+        .unwrap_or("/")
+}
+
+fn ingress_path_type(spec: &RustOperatorSpec) -> &str {
+    spec.ingress_path_type
+        .as_deref()
+        .filter(|p| !p.trim().is_empty())
+        .unwrap_or("Prefix")
+}
+
+/// The explicitly selected backend port: a number if it parses as one, otherwise a port name.
+fn ingress_backend_port(spec: &RustOperatorSpec) -> Option<ServiceBackendPort> {
+    let port = spec
+        .ingress_backend_port
+        .as_deref()
+        .map(str::trim)
+        .filter(|p| !p.is_empty())?;
+    Some(match port.parse::<i32>() {
+        Ok(n) => ServiceBackendPort {
+            number: Some(n),
+            name: None,
+        },
+        Err(_) => ServiceBackendPort {
+            number: None,
+            name: Some(port.to_string()),
+        },
+    })
+}
+
 pub fn desired_ingress(
     name: &str,
     labels: &BTreeMap<String, String>,
     svc_name: &str,
-    host: &str,
+    hosts: &[&str],
     tls_secret: &str,
     owner: k8s_openapi::apimachinery::pkg::apis::meta::v1::OwnerReference,
+    spec: &RustOperatorSpec,
 ) -> Ingress {
     let backend = IngressBackend {
         service: Some(IngressServiceBackend {
             name: svc_name.to_string(),
-            port: Some(ServiceBackendPort {
-                number: Some(80),
-                name: None,
-            }),
+            port: Some(ingress_backend_port(spec).unwrap_or_else(|| {
+                let primary = primary_port(spec);
+                ServiceBackendPort {
+                    number: Some(primary.service_port.unwrap_or(primary.container_port)),
+                    name: None,
+                }
+            })),
         }),
         resource: None,
     };
 
     let path = HTTPIngressPath {
         backend,
-        path: Some("/".into()),
-        path_type: "Prefix".into(),
+        path: Some(ingress_path(spec).into()),
+        path_type: ingress_path_type(spec).into(),
     };
 
-    let rule = IngressRule {
-        host: Some(host.to_string()),
-        http: Some(HTTPIngressRuleValue { paths: vec![path] }),
-    };
+    let rules = hosts
+        .iter()
+        .map(|host| IngressRule {
+            host: Some(host.to_string()),
+            http: Some(HTTPIngressRuleValue {
+                paths: vec![path.clone()],
+            }),
+        })
+        .collect();
+
+    let mut annotations = owner_annotations(&owner);
+    for (k, v) in &spec.ingress_annotations {
+        annotations.entry(k.clone()).or_insert_with(|| v.clone());
+    }
 
     let tls = if tls_secret.is_empty() {
         None
     } else {
         Some(vec![IngressTLS {
-            hosts: Some(vec![host.to_string()]),
+            hosts: Some(hosts.iter().map(|h| h.to_string()).collect()),
             secret_name: Some(tls_secret.to_string()),
         }])
     };
@@ -198,12 +1281,16 @@ pub fn desired_ingress(
         metadata: ObjectMeta {
             name: Some(name.to_string()),
             labels: Some(labels.clone()),
+            annotations: Some(annotations),
             owner_references: Some(vec![owner]),
             ..Default::default()
         },
         spec: Some(IngressSpec {
-            ingress_class_name: None,
-            rules: Some(vec![rule]),
+            ingress_class_name: spec
+                .ingress_class_name
+                .clone()
+                .filter(|c| !c.trim().is_empty()),
+            rules: Some(rules),
             tls,
             ..Default::default()
         }),
@@ -211,10 +1298,1575 @@ pub fn desired_ingress(
     }
 }
 
-pub fn upsert_condition(list: &mut Vec<crate::crd::HwCondition>, newc: crate::crd::HwCondition) {
-    if let Some(i) = list.iter().position(|c| c.type_ == newc.type_) {
-        list[i] = newc;
+/// `"3"` or `"25%"`.
+fn is_count_or_percent(v: &str) -> bool {
+    v.parse::<u32>().is_ok()
+        || v.strip_suffix('%')
+            .is_some_and(|p| p.parse::<u32>().is_ok())
+}
+
+/// `"3"` as a count, anything else (e.g. `"25%"`) as a percentage string.
+fn int_or_percent(v: &str) -> IntOrString {
+    v.parse()
+        .map_or_else(|_| IntOrString::String(v.into()), IntOrString::Int)
+}
+
+fn desired_strategy(s: &StrategySpec) -> DeploymentStrategy {
+    DeploymentStrategy {
+        type_: Some(s.type_.clone()),
+        rolling_update: (s.type_ == "RollingUpdate").then(|| RollingUpdateDeployment {
+            max_surge: s.max_surge.as_deref().map(int_or_percent),
+            max_unavailable: s.max_unavailable.as_deref().map(int_or_percent),
+        }),
+    }
+}
+
+/// HPA scaling the Deployment `name` between the configured bounds.
+pub fn desired_hpa(
+    name: &str,
+    labels: &BTreeMap<String, String>,
+    autoscaling: &AutoscalingSpec,
+    owner: k8s_openapi::apimachinery::pkg::apis::meta::v1::OwnerReference,
+) -> HorizontalPodAutoscaler {
+    HorizontalPodAutoscaler {
+        metadata: ObjectMeta {
+            name: Some(name.to_string()),
+            labels: Some(labels.clone()),
+            annotations: Some(owner_annotations(&owner)),
+            owner_references: Some(vec![owner]),
+            ..Default::default()
+        },
+        spec: Some(HorizontalPodAutoscalerSpec {
+            scale_target_ref: CrossVersionObjectReference {
+                api_version: Some("apps/v1".into()),
+                kind: "Deployment".into(),
+                name: name.to_string(),
+            },
+            min_replicas: Some(autoscaling.min_replicas.unwrap_or(1)),
+            max_replicas: autoscaling.max_replicas,
+            metrics: Some(vec![MetricSpec {
+                type_: "Resource".into(),
+                resource: Some(ResourceMetricSource {
+                    name: "cpu".into(),
+                    target: MetricTarget {
+                        type_: "Utilization".into(),
+                        average_utilization: Some(autoscaling.target_cpu_utilization.unwrap_or(80)),
+                        ..Default::default()
+                    },
+                }),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+/// ServiceAccount `sa_name` created for the pods with `create_service_account`.
+pub fn desired_service_account(
+    sa_name: &str,
+    labels: &BTreeMap<String, String>,
+    owner: k8s_openapi::apimachinery::pkg::apis::meta::v1::OwnerReference,
+) -> ServiceAccount {
+    ServiceAccount {
+        metadata: ObjectMeta {
+            name: Some(sa_name.to_string()),
+            labels: Some(labels.clone()),
+            annotations: Some(owner_annotations(&owner)),
+            owner_references: Some(vec![owner]),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+/// PDB keeping `min_available` of the pods selected by `labels` up during voluntary disruptions.
+pub fn desired_pdb(
+    name: &str,
+    labels: &BTreeMap<String, String>,
+    min_available: &str,
+    owner: k8s_openapi::apimachinery::pkg::apis::meta::v1::OwnerReference,
+) -> PodDisruptionBudget {
+    PodDisruptionBudget {
+        metadata: ObjectMeta {
+            name: Some(name.to_string()),
+            labels: Some(labels.clone()),
+            annotations: Some(owner_annotations(&owner)),
+            owner_references: Some(vec![owner]),
+            ..Default::default()
+        },
+        spec: Some(PodDisruptionBudgetSpec {
+            min_available: Some(int_or_percent(min_available)),
+            selector: Some(
+                k8s_openapi::apimachinery::pkg::apis::meta::v1::LabelSelector {
+                    match_labels: Some(labels.clone()),
+                    ..Default::default()
+                },
+            ),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+/// NetworkPolicy admitting ingress to the pods selected by `labels` from anywhere, but only on
+/// the container ports of `ports(spec)`.
+pub fn desired_network_policy(
+    name: &str,
+    labels: &BTreeMap<String, String>,
+    owner: k8s_openapi::apimachinery::pkg::apis::meta::v1::OwnerReference,
+    spec: &RustOperatorSpec,
+) -> NetworkPolicy {
+    NetworkPolicy {
+        metadata: ObjectMeta {
+            name: Some(name.to_string()),
+            labels: Some(labels.clone()),
+            annotations: Some(owner_annotations(&owner)),
+            owner_references: Some(vec![owner]),
+            ..Default::default()
+        },
+        spec: Some(NetworkPolicySpec {
+            pod_selector: Some(
+                k8s_openapi::apimachinery::pkg::apis::meta::v1::LabelSelector {
+                    match_labels: Some(labels.clone()),
+                    ..Default::default()
+                },
+            ),
+            policy_types: Some(vec!["Ingress".into()]),
+            ingress: Some(vec![NetworkPolicyIngressRule {
+                ports: Some(
+                    ports(spec)
+                        .into_iter()
+                        .map(|p| NetworkPolicyPort {
+                            port: Some(IntOrString::Int(p.container_port)),
+                            protocol: Some("TCP".into()),
+                            ..Default::default()
+                        })
+                        .collect(),
+                ),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        }),
+    }
+}
+
+/// Every child a reconcile of `spec` applies; `None` marks a child that should not exist.
+pub struct DesiredState {
+    pub configmap: ConfigMap,
+    pub deployment: Option<Deployment>,
+    pub hpa: Option<HorizontalPodAutoscaler>,
+    pub pdb: Option<PodDisruptionBudget>,
+    pub network_policy: Option<NetworkPolicy>,
+    pub service_account: Option<ServiceAccount>,
+    pub service: Option<Service>,
+    pub ingress: Option<Ingress>,
+}
+
+impl DesiredState {
+    /// The children this state applies, sorted so the status list is stable.
+    pub fn managed_refs(&self) -> Vec<ManagedRef> {
+        let children = [
+            ("ConfigMap", Some(self.configmap.name_any())),
+            ("Deployment", self.deployment.as_ref().map(|d| d.name_any())),
+            (
+                "HorizontalPodAutoscaler",
+                self.hpa.as_ref().map(|h| h.name_any()),
+            ),
+            (
+                "PodDisruptionBudget",
+                self.pdb.as_ref().map(|p| p.name_any()),
+            ),
+            (
+                "NetworkPolicy",
+                self.network_policy.as_ref().map(|n| n.name_any()),
+            ),
+            (
+                "ServiceAccount",
+                self.service_account.as_ref().map(|s| s.name_any()),
+            ),
+            ("Service", self.service.as_ref().map(|s| s.name_any())),
+            ("Ingress", self.ingress.as_ref().map(|i| i.name_any())),
+        ];
+        let mut refs: Vec<ManagedRef> = children
+            .into_iter()
+            .filter_map(|(kind, name)| {
+                name.map(|name| ManagedRef {
+                    kind: kind.into(),
+                    name,
+                })
+            })
+            .collect();
+        refs.sort();
+        refs
+    }
+}
+
+/// Validates `spec` and renders all children in one place.
+pub fn build_desired_state(
+    spec: &RustOperatorSpec,
+    name: &str,
+    generation: Option<i64>,
+    owner: k8s_openapi::apimachinery::pkg::apis::meta::v1::OwnerReference,
+) -> Result<DesiredState, String> {
+    validate_spec(spec)?;
+    let labels = labels(name);
+    let config_only = spec.mode == "config-only";
+    let svc_name = if spec.manage_service {
+        format!("{name}-service")
     } else {
-        list.push(newc);
+        spec.ingress_backend_service.clone().unwrap_or_default()
+    };
+    let mut desired = DesiredState {
+        configmap: desired_configmap(
+            name,
+            &labels,
+            &spec.html,
+            configmap_key(spec),
+            &spec.files,
+            owner.clone(),
+        ),
+        // The HPA owns the replica count, so leave it out of the Deployment we apply.
+        deployment: (!config_only).then(|| {
+            let replicas = spec.autoscaling.is_none().then_some(spec.replicas);
+            let revision = generation.filter(|_| spec.annotate_revision);
+            desired_deployment(name, &labels, replicas, revision, owner.clone(), spec)
+        }),
+        hpa: spec
+            .autoscaling
+            .as_ref()
+            .filter(|_| !config_only)
+            .map(|a| desired_hpa(name, &labels, a, owner.clone())),
+        pdb: spec
+            .pdb_min_available
+            .as_deref()
+            .filter(|_| !config_only)
+            .map(|m| desired_pdb(name, &labels, m, owner.clone())),
+        network_policy: (spec.network_policy && !config_only)
+            .then(|| desired_network_policy(name, &labels, owner.clone(), spec)),
+        service_account: service_account_name(spec)
+            .filter(|_| spec.create_service_account && !config_only)
+            .map(|sa| desired_service_account(sa, &labels, owner.clone())),
+        service: spec
+            .manage_service
+            .then(|| desired_service(name, &labels, &spec.service_type, owner.clone(), spec)),
+        ingress: (!ingress_hosts(spec).is_empty()).then(|| {
+            desired_ingress(
+                name,
+                &labels,
+                &svc_name,
+                &ingress_hosts(spec),
+                &spec.tls_secret_name,
+                owner,
+                spec,
+            )
+        }),
+    };
+    let children = [
+        ("ConfigMap", Some(desired.configmap.meta_mut())),
+        (
+            "Deployment",
+            desired.deployment.as_mut().map(|d| d.meta_mut()),
+        ),
+        (
+            "HorizontalPodAutoscaler",
+            desired.hpa.as_mut().map(|h| h.meta_mut()),
+        ),
+        (
+            "PodDisruptionBudget",
+            desired.pdb.as_mut().map(|p| p.meta_mut()),
+        ),
+        (
+            "NetworkPolicy",
+            desired.network_policy.as_mut().map(|n| n.meta_mut()),
+        ),
+        (
+            "ServiceAccount",
+            desired.service_account.as_mut().map(|s| s.meta_mut()),
+        ),
+        ("Service", desired.service.as_mut().map(|s| s.meta_mut())),
+        ("Ingress", desired.ingress.as_mut().map(|i| i.meta_mut())),
+    ];
+    for (kind, meta) in children {
+        let Some(meta) = meta else { continue };
+        // Per-kind annotations go first so they win over `extra_annotations`.
+        if let Some(extra) = spec.child_annotations.get(kind) {
+            let map = meta.annotations.get_or_insert_with(BTreeMap::new);
+            for (k, v) in extra {
+                map.entry(k.clone()).or_insert_with(|| v.clone());
+            }
+        }
+        add_extra_metadata(meta, spec);
+    }
+    Ok(desired)
+}
+
+/// Merges `extra_labels`/`extra_annotations` into a child's metadata without overriding keys
+/// the operator already set. Selectors and pod template labels are left alone.
+fn add_extra_metadata(meta: &mut ObjectMeta, spec: &RustOperatorSpec) {
+    for (target, extra) in [
+        (&mut meta.labels, &spec.extra_labels),
+        (&mut meta.annotations, &spec.extra_annotations),
+    ] {
+        if extra.is_empty() {
+            continue;
+        }
+        let map = target.get_or_insert_with(BTreeMap::new);
+        for (k, v) in extra {
+            map.entry(k.clone()).or_insert_with(|| v.clone());
+        }
+    }
+}
+
+/// A TLS Secret is usable once it carries a non-empty `tls.crt` and `tls.key`.
+pub fn tls_secret_ready(secret: Option<&Secret>) -> bool {
+    let Some(data) = secret.and_then(|s| s.data.as_ref()) else {
+        return false;
+    };
+    ["tls.crt", "tls.key"]
+        .iter()
+        .all(|k| data.get(*k).is_some_and(|v| !v.0.is_empty()))
+}
+
+/// `spec.message` as stored in `status.observed_message`: control characters (newlines, ANSI
+/// escapes, ...) are escaped so the value can't garble terminals or logs, and the result is capped
+/// at `MAX_MESSAGE_CHARS`. Ordinary text, including non-ASCII, passes through unchanged.
+pub fn sanitize_message(message: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in message.chars().enumerate() {
+        if i == MAX_MESSAGE_CHARS {
+            out.push('…');
+            break;
+        }
+        if c.is_control() {
+            out.extend(c.escape_default());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// When the CR expires under `ttl_seconds_after_ready`: that long after `Ready` last turned True.
+/// `None` without a TTL or while not Ready.
+pub fn ttl_expiry(
+    spec: &RustOperatorSpec,
+    conditions: &[crate::crd::HwCondition],
+) -> Option<DateTime<Utc>> {
+    let ttl = spec.ttl_seconds_after_ready?;
+    let ready = conditions
+        .iter()
+        .find(|c| c.type_ == "Ready" && c.status == "True")?;
+    let since = DateTime::parse_from_rfc3339(ready.last_transition_time.as_deref()?).ok()?;
+    Some(since.with_timezone(&Utc) + TimeDelta::seconds(ttl))
+}
+
+/// Replaces the condition of the same type, keeping its `last_transition_time` unless the status
+/// flipped (so unchanged conditions don't cause a status patch every reconcile).
+pub fn upsert_condition(
+    list: &mut Vec<crate::crd::HwCondition>,
+    mut newc: crate::crd::HwCondition,
+) {
+    let existing = list.iter().position(|c| c.type_ == newc.type_);
+    newc.last_transition_time = match existing.map(|i| &list[i]) {
+        Some(old) if old.status == newc.status && old.last_transition_time.is_some() => {
+            old.last_transition_time.clone()
+        }
+        _ => Some(
+            k8s_openapi::chrono::Utc::now()
+                .to_rfc3339_opts(k8s_openapi::chrono::SecondsFormat::Secs, true),
+        ),
+    };
+    match existing {
+        Some(i) => list[i] = newc,
+        None => list.push(newc),
+    }
+}
+
+pub fn pod_statuses(pods: &[Pod]) -> Vec<PodStatusEntry> {
+    let mut entries: Vec<PodStatusEntry> = pods
+        .iter()
+        .map(|p| PodStatusEntry {
+            name: p.metadata.name.clone().unwrap_or_default(),
+            phase: p
+                .status
+                .as_ref()
+                .and_then(|s| s.phase.clone())
+                .unwrap_or_else(|| "Unknown".into()),
+        })
+        .collect();
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+    entries.truncate(MAX_POD_STATUSES);
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::OwnerReference;
+    use serde_json::json;
+
+    use super::*;
+
+    /// A spec as the API server would store it: serde defaults plus the given fields.
+    fn spec(fields: serde_json::Value) -> RustOperatorSpec {
+        let mut value = json!({ "message": "hello" });
+        value
+            .as_object_mut()
+            .unwrap()
+            .extend(fields.as_object().unwrap().clone());
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn owner() -> OwnerReference {
+        OwnerReference {
+            api_version: "rootster.xyz/v1".into(),
+            kind: "RustOperator".into(),
+            name: "web".into(),
+            uid: "uid-1".into(),
+            controller: Some(true),
+            ..Default::default()
+        }
+    }
+
+    fn desired(spec: &RustOperatorSpec) -> DesiredState {
+        build_desired_state(spec, "web", Some(1), owner()).unwrap()
+    }
+
+    fn pod_spec(spec: &RustOperatorSpec) -> PodSpec {
+        desired(spec)
+            .deployment
+            .unwrap()
+            .spec
+            .unwrap()
+            .template
+            .spec
+            .unwrap()
+    }
+
+    /// A spec that renders every kind of child.
+    fn every_child() -> RustOperatorSpec {
+        spec(json!({
+            "ingress_host": "web.example.com",
+            "autoscaling": { "max_replicas": 3 },
+            "pdb_min_available": "1",
+            "network_policy": true,
+            "service_account": "web-sa",
+            "create_service_account": true,
+        }))
+    }
+
+    /// Metadata of every child in `state`.
+    fn child_metas(state: &DesiredState) -> Vec<&ObjectMeta> {
+        let mut metas = vec![&state.configmap.metadata];
+        metas.extend(state.deployment.as_ref().map(|d| &d.metadata));
+        metas.extend(state.hpa.as_ref().map(|h| &h.metadata));
+        metas.extend(state.pdb.as_ref().map(|p| &p.metadata));
+        metas.extend(state.network_policy.as_ref().map(|n| &n.metadata));
+        metas.extend(state.service_account.as_ref().map(|s| &s.metadata));
+        metas.extend(state.service.as_ref().map(|s| &s.metadata));
+        metas.extend(state.ingress.as_ref().map(|i| &i.metadata));
+        metas
+    }
+
+    /// The rollout fingerprint stamped on the pod template.
+    fn fingerprint(spec: &RustOperatorSpec) -> String {
+        let deploy = desired(spec).deployment.unwrap();
+        deploy
+            .spec
+            .unwrap()
+            .template
+            .metadata
+            .unwrap()
+            .annotations
+            .unwrap()[ROLLOUT_HASH_ANNOTATION]
+            .clone()
+    }
+
+    #[test]
+    fn every_child_carries_the_owner_uid_annotation() {
+        let state = desired(&every_child());
+        let metas = child_metas(&state);
+        assert_eq!(metas.len(), 8);
+        for meta in metas {
+            let annotations = meta.annotations.as_ref().unwrap();
+            assert_eq!(
+                annotations[OWNER_UID_ANNOTATION],
+                owner().uid,
+                "{:?}",
+                meta.name
+            );
+        }
+    }
+
+    #[test]
+    fn env_vars_are_sorted_and_complete() {
+        let with_env = spec(json!({ "env": { "B": "2", "A": "1", "C": "" } }));
+        let env = pod_spec(&with_env).containers[0].env.clone().unwrap();
+        let pairs: Vec<_> = env
+            .iter()
+            .map(|e| (e.name.as_str(), e.value.as_deref().unwrap()))
+            .collect();
+        assert_eq!(pairs, [("A", "1"), ("B", "2"), ("C", "")]);
+
+        assert!(pod_spec(&spec(json!({}))).containers[0].env.is_none());
+        assert_ne!(fingerprint(&with_env), fingerprint(&spec(json!({}))));
+    }
+
+    #[test]
+    fn resource_requirements_match_the_spec() {
+        let limited = spec(json!({
+            "resources": { "cpu_request": "100m", "memory_limit": "128Mi" },
+        }));
+        let res = pod_spec(&limited).containers[0].resources.clone().unwrap();
+        assert_eq!(
+            res.requests,
+            Some(BTreeMap::from([("cpu".into(), Quantity("100m".into()))]))
+        );
+        assert_eq!(
+            res.limits,
+            Some(BTreeMap::from([(
+                "memory".into(),
+                Quantity("128Mi".into())
+            )]))
+        );
+
+        let empty = spec(json!({ "resources": { "cpu_limit": "" } }));
+        assert!(pod_spec(&empty).containers[0].resources.is_none());
+        assert_eq!(fingerprint(&empty), fingerprint(&spec(json!({}))));
+        assert_ne!(fingerprint(&limited), fingerprint(&spec(json!({}))));
+    }
+
+    #[test]
+    fn container_port_reaches_deployment_and_service() {
+        let custom = spec(json!({ "container_port": 8080, "service_port": 8000 }));
+        let pod = pod_spec(&custom);
+        let ports = pod.containers[0].ports.clone().unwrap();
+        assert_eq!(ports[0].container_port, 8080);
+
+        let svc_ports = service_spec(&custom).ports.unwrap();
+        assert_eq!(svc_ports[0].target_port, Some(IntOrString::Int(8080)));
+        assert_eq!(svc_ports[0].port, 8000);
+
+        let defaults = service_spec(&spec(json!({}))).ports.unwrap();
+        assert_eq!(
+            (defaults[0].port, defaults[0].target_port.clone()),
+            (80, Some(IntOrString::Int(80)))
+        );
+    }
+
+    #[test]
+    fn duplicate_port_names_or_numbers_are_rejected() {
+        let with_ports =
+            |extra: serde_json::Value| validate_spec(&spec(json!({ "extra_ports": extra })));
+        assert!(with_ports(json!([{ "name": "metrics", "container_port": 9090 }])).is_ok());
+        let dup_name = with_ports(json!([{ "name": "http", "container_port": 9090 }]));
+        assert!(
+            dup_name
+                .unwrap_err()
+                .contains("port name \"http\" is used more than once")
+        );
+        assert!(
+            with_ports(json!([{ "name": "alt", "container_port": 80, "service_port": 81 }]))
+                .is_err()
+        );
+        assert!(
+            with_ports(json!([{ "name": "alt", "container_port": 81, "service_port": 80 }]))
+                .is_err()
+        );
+    }
+
+    fn service_spec(spec: &RustOperatorSpec) -> ServiceSpec {
+        desired(spec).service.unwrap().spec.unwrap()
+    }
+
+    #[test]
+    fn share_process_namespace_reaches_the_pod_spec() {
+        let pod = pod_spec(&spec(json!({ "share_process_namespace": true })));
+        assert_eq!(pod.share_process_namespace, Some(true));
+        assert_eq!(pod_spec(&spec(json!({}))).share_process_namespace, None);
+    }
+
+    #[test]
+    fn container_image_matches_the_spec() {
+        let image = |fields| pod_spec(&spec(fields)).containers[0].image.clone().unwrap();
+        assert_eq!(image(json!({})), "nginx:1.27-alpine");
+        assert_eq!(
+            image(json!({ "image": "ghcr.io/acme/site", "image_tag": "v2" })),
+            "ghcr.io/acme/site:v2"
+        );
+        assert_eq!(image(json!({ "image_tag": "1.29" })), "nginx:1.29");
+
+        let pinned = fingerprint(&spec(json!({})));
+        assert_ne!(pinned, fingerprint(&spec(json!({ "image_tag": "1.29" }))));
+        let explicit = spec(json!({ "image": DEFAULT_IMAGE, "image_tag": DEFAULT_IMAGE_TAG }));
+        assert_eq!(pinned, fingerprint(&explicit));
+    }
+
+    #[test]
+    fn default_spec_keeps_the_html_only_fingerprint() {
+        let s = spec(json!({}));
+        let mut h = Sha256::new();
+        h.update(serde_json::to_vec(&json!({ "html": s.html })).unwrap());
+        assert_eq!(fingerprint(&s), format!("{:x}", h.finalize()));
+    }
+
+    #[test]
+    fn enable_service_links_false_reaches_the_pod_spec() {
+        let pod = pod_spec(&spec(json!({ "enable_service_links": false })));
+        assert_eq!(pod.enable_service_links, Some(false));
+        assert_eq!(pod_spec(&spec(json!({}))).enable_service_links, None);
+    }
+
+    #[test]
+    fn custom_container_name_renders() {
+        let pod = pod_spec(&spec(json!({ "container_name": "web" })));
+        assert_eq!(pod.containers[0].name, "web");
+        assert_eq!(
+            pod_spec(&spec(json!({}))).containers[0].name,
+            DEFAULT_CONTAINER_NAME
+        );
+        assert!(validate_spec(&spec(json!({ "container_name": "Web_1" }))).is_err());
+    }
+
+    #[test]
+    fn ingress_without_managed_service_needs_a_backend() {
+        let unmanaged = json!({ "manage_service": false, "ingress_host": "web.example.com" });
+        assert!(validate_spec(&spec(unmanaged.clone())).is_err());
+
+        let mut external = unmanaged;
+        external["ingress_backend_service"] = json!("mesh-gateway");
+        let state = desired(&spec(external));
+        assert!(state.service.is_none());
+        let ingress = state.ingress.unwrap().spec.unwrap();
+        let backend = ingress.rules.unwrap()[0].http.as_ref().unwrap().paths[0]
+            .backend
+            .service
+            .clone()
+            .unwrap();
+        assert_eq!(backend.name, "mesh-gateway");
+    }
+
+    #[test]
+    fn native_sidecar_renders_as_an_always_restarting_init_container() {
+        let pod = pod_spec(&spec(json!({
+            "sidecars": [
+                { "name": "proxy", "image": "envoy", "native": true },
+                { "name": "logs", "image": "fluent-bit" },
+            ],
+        })));
+        let init = pod.init_containers.unwrap();
+        assert_eq!(init.len(), 1);
+        assert_eq!(init[0].name, "proxy");
+        assert_eq!(init[0].restart_policy.as_deref(), Some("Always"));
+        let names: Vec<_> = pod.containers.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, [DEFAULT_CONTAINER_NAME, "logs"]);
+        assert_eq!(pod.containers[1].restart_policy, None);
+    }
+
+    #[test]
+    fn projected_extra_volume_is_appended() {
+        let projected = spec(json!({
+            "extra_volumes": [{
+                "name": "creds",
+                "projected": { "sources": [{ "secret": { "name": "db" } }] },
+            }],
+            "extra_volume_mounts": [{ "name": "creds", "mountPath": "/var/run/creds" }],
+        }));
+        let pod = pod_spec(&projected);
+        let volume = pod.volumes.unwrap().pop().unwrap();
+        assert_eq!(volume.name, "creds");
+        let sources = volume.projected.unwrap().sources.unwrap();
+        assert_eq!(sources[0].secret.as_ref().unwrap().name, "db");
+        let mounts = pod.containers[0].volume_mounts.as_ref().unwrap();
+        assert!(mounts.iter().any(|m| m.mount_path == "/var/run/creds"));
+
+        let clash = json!({ "extra_volumes": [{ "name": HTML_VOLUME, "emptyDir": {} }] });
+        assert!(validate_spec(&spec(clash)).is_err());
+        let malformed = json!({ "extra_volumes": [{ "projected": "nope" }] });
+        assert!(validate_spec(&spec(malformed)).is_err());
+    }
+
+    #[test]
+    fn liveness_and_readiness_probes_are_populated() {
+        let path = |probe: Option<Probe>| probe.unwrap().http_get.unwrap().path.unwrap();
+        let defaults = pod_spec(&spec(json!({ "container_port": 8080 }))).containers[0].clone();
+        let liveness = defaults.liveness_probe.clone().unwrap();
+        assert_eq!(path(defaults.liveness_probe), "/");
+        assert_eq!(path(defaults.readiness_probe), "/");
+        assert_eq!(liveness.http_get.unwrap().port, IntOrString::Int(8080));
+        assert_eq!(
+            (liveness.initial_delay_seconds, liveness.period_seconds),
+            (Some(10), Some(10))
+        );
+
+        let custom = pod_spec(&spec(json!({
+            "liveness_probe": { "http_path": "/live" },
+            "readiness_probe": { "http_path": "/ready", "period_seconds": 1 },
+        })))
+        .containers[0]
+            .clone();
+        assert_eq!(
+            custom.readiness_probe.as_ref().unwrap().period_seconds,
+            Some(1)
+        );
+        assert_eq!(path(custom.liveness_probe), "/live");
+        assert_eq!(path(custom.readiness_probe), "/ready");
+    }
+
+    #[test]
+    fn startup_probe_renders_with_a_high_failure_threshold() {
+        let pod = pod_spec(&spec(json!({
+            "startup_probe": { "http_path": "/healthz", "failure_threshold": 60, "period_seconds": 5 },
+        })));
+        let probe = pod.containers[0].startup_probe.clone().unwrap();
+        assert_eq!(probe.failure_threshold, Some(60));
+        assert_eq!(probe.period_seconds, Some(5));
+        let get = probe.http_get.unwrap();
+        assert_eq!(get.path.as_deref(), Some("/healthz"));
+        assert_eq!(get.port, IntOrString::Int(80));
+        assert!(
+            pod_spec(&spec(json!({}))).containers[0]
+                .startup_probe
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn desired_state_for_a_tls_ingress() {
+        let state = desired(&spec(json!({
+            "ingress_host": "web.example.com",
+            "tls_secret_name": "web-tls",
+        })));
+        assert_eq!(state.configmap.metadata.name.as_deref(), Some("web"));
+        assert!(state.deployment.is_some());
+        assert_eq!(
+            state.service.unwrap().metadata.name.as_deref(),
+            Some("web-service")
+        );
+        let ingress = state.ingress.unwrap().spec.unwrap();
+        let tls = &ingress.tls.unwrap()[0];
+        assert_eq!(tls.secret_name.as_deref(), Some("web-tls"));
+        assert_eq!(
+            tls.hosts.as_deref(),
+            Some(&["web.example.com".to_string()][..])
+        );
+        assert_eq!(
+            ingress.rules.unwrap()[0].host.as_deref(),
+            Some("web.example.com")
+        );
+
+        let invalid = spec(json!({ "replicas": -1 }));
+        assert!(build_desired_state(&invalid, "web", None, owner()).is_err());
+    }
+
+    #[test]
+    fn desired_hpa_targets_the_deployment() {
+        let state = desired(&spec(json!({
+            "replicas": 2,
+            "autoscaling": { "min_replicas": 2, "max_replicas": 5, "target_cpu_utilization": 60 },
+        })));
+        let hpa = state.hpa.unwrap().spec.unwrap();
+        assert_eq!(hpa.scale_target_ref.kind, "Deployment");
+        assert_eq!(hpa.scale_target_ref.name, "web");
+        assert_eq!((hpa.min_replicas, hpa.max_replicas), (Some(2), 5));
+        let target = &hpa.metrics.unwrap()[0].resource.clone().unwrap();
+        assert_eq!(target.name, "cpu");
+        assert_eq!(target.target.average_utilization, Some(60));
+        // The HPA owns the replica count.
+        assert_eq!(state.deployment.unwrap().spec.unwrap().replicas, None);
+
+        let fixed = desired(&spec(json!({ "replicas": 2 })));
+        assert!(fixed.hpa.is_none());
+        assert_eq!(fixed.deployment.unwrap().spec.unwrap().replicas, Some(2));
+    }
+
+    #[test]
+    fn pod_statuses_lists_each_pod() {
+        let pod = |name: &str, phase: Option<&str>| Pod {
+            metadata: ObjectMeta {
+                name: Some(name.into()),
+                ..Default::default()
+            },
+            status: Some(k8s_openapi::api::core::v1::PodStatus {
+                phase: phase.map(String::from),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let entries = pod_statuses(&[pod("web-b", None), pod("web-a", Some("Running"))]);
+        assert_eq!(
+            entries,
+            [
+                PodStatusEntry {
+                    name: "web-a".into(),
+                    phase: "Running".into(),
+                },
+                PodStatusEntry {
+                    name: "web-b".into(),
+                    phase: "Unknown".into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn rendering_is_byte_identical() {
+        let spec = spec(json!({
+            "env": { "ZONE": "eu", "APP_MODE": "prod", "LOG": "info" },
+            "node_selector": { "zone": "a", "disk": "ssd" },
+            "extra_labels": { "team": "web", "cost": "42" },
+            "extra_annotations": { "b": "2", "a": "1" },
+            "files": { "style.css": "body {}", "about.html": "<p>hi</p>" },
+        }));
+        let render = || serde_json::to_vec(&desired(&spec).deployment.unwrap()).unwrap();
+        assert_eq!(render(), render());
+
+        let deploy = desired(&spec).deployment.unwrap();
+        let env = deploy.spec.unwrap().template.spec.unwrap().containers[0]
+            .env
+            .clone()
+            .unwrap();
+        let names: Vec<_> = env.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["APP_MODE", "LOG", "ZONE"]);
+    }
+
+    #[test]
+    fn custom_configmap_key_is_used_for_data_and_mount() {
+        let state = desired(&spec(json!({ "configmap_key": "page.txt", "html": "hi" })));
+        let data = state.configmap.data.unwrap();
+        assert_eq!(data.keys().collect::<Vec<_>>(), ["page.txt"]);
+        assert_eq!(data["page.txt"], "hi");
+
+        let pod = state
+            .deployment
+            .unwrap()
+            .spec
+            .unwrap()
+            .template
+            .spec
+            .unwrap();
+        let html = pod
+            .volumes
+            .unwrap()
+            .into_iter()
+            .find(|v| v.name == HTML_VOLUME);
+        let items = html.unwrap().config_map.unwrap().items.unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(
+            (items[0].key.as_str(), items[0].path.as_str()),
+            ("page.txt", "page.txt")
+        );
+    }
+
+    #[test]
+    fn local_internal_traffic_policy_renders() {
+        let svc = service_spec(&spec(json!({ "internal_traffic_policy": "Local" })));
+        assert_eq!(svc.internal_traffic_policy.as_deref(), Some("Local"));
+        assert!(validate_spec(&spec(json!({ "internal_traffic_policy": "Node" }))).is_err());
+    }
+
+    #[test]
+    fn pinned_cluster_ip_renders() {
+        let svc = service_spec(&spec(json!({ "cluster_ip": "10.96.0.42" })));
+        assert_eq!(svc.cluster_ip.as_deref(), Some("10.96.0.42"));
+        assert!(validate_spec(&spec(json!({ "cluster_ip": "None" }))).is_err());
+        assert!(validate_spec(&spec(json!({ "cluster_ip": "10.96.0" }))).is_err());
+    }
+
+    #[test]
+    fn managed_resources_follow_the_ingress() {
+        let kinds = |spec: &RustOperatorSpec| -> Vec<(String, String)> {
+            desired(spec)
+                .managed_refs()
+                .into_iter()
+                .map(|r| (r.kind, r.name))
+                .collect()
+        };
+        let base = [
+            ("ConfigMap", "web"),
+            ("Deployment", "web"),
+            ("Service", "web-service"),
+        ]
+        .map(|(k, n)| (k.to_string(), n.to_string()));
+        assert_eq!(kinds(&spec(json!({}))), base);
+
+        let with_ingress = kinds(&spec(json!({ "ingress_host": "web.example.com" })));
+        let mut expected = base.to_vec();
+        expected.insert(2, ("Ingress".into(), "web".into()));
+        assert_eq!(with_ingress, expected);
+    }
+
+    #[test]
+    fn service_type_accepts_only_the_supported_values() {
+        for ok in ["ClusterIP", "NodePort", "LoadBalancer"] {
+            assert!(validate_spec(&spec(json!({ "service_type": ok }))).is_ok());
+        }
+        for bad in ["ExternalName", "clusterip", ""] {
+            let err = validate_spec(&spec(json!({ "service_type": bad }))).unwrap_err();
+            assert!(err.starts_with(&format!("service_type {bad:?}")), "{err}");
+        }
+    }
+
+    #[test]
+    fn extra_files_become_configmap_keys() {
+        let files = json!({ "style.css": "body {}", "about.html": "<p>about</p>" });
+        let with_files = spec(json!({ "html": "<p>home</p>", "files": files }));
+        let data = desired(&with_files).configmap.data.unwrap();
+        let keys: Vec<_> = data.keys().map(String::as_str).collect();
+        assert_eq!(keys, ["about.html", "index.html", "style.css"]);
+
+        let clash = spec(json!({ "html": "<p>home</p>", "files": { "index.html": "other" } }));
+        let data = desired(&clash).configmap.data.unwrap();
+        assert_eq!(data["index.html"], "<p>home</p>");
+
+        let without = spec(json!({ "html": "<p>home</p>" }));
+        assert_ne!(fingerprint(&with_files), fingerprint(&without));
+    }
+
+    #[test]
+    fn transition_time_changes_only_when_status_flips() {
+        use crate::crd::HwCondition;
+
+        const EARLIER: &str = "2020-01-01T00:00:00Z";
+        let cond = |status: &str| HwCondition {
+            type_: "Available".into(),
+            status: status.into(),
+            ..Default::default()
+        };
+        let mut list = vec![HwCondition {
+            last_transition_time: Some(EARLIER.into()),
+            ..cond("True")
+        }];
+
+        upsert_condition(&mut list, cond("True"));
+        assert_eq!(list[0].last_transition_time.as_deref(), Some(EARLIER));
+
+        upsert_condition(&mut list, cond("False"));
+        assert_eq!(list.len(), 1);
+        let flipped = list[0].last_transition_time.clone().unwrap();
+        assert_ne!(flipped, EARLIER);
+
+        upsert_condition(
+            &mut list,
+            HwCondition {
+                type_: "Progressing".into(),
+                ..cond("True")
+            },
+        );
+        assert_eq!(list.len(), 2);
+        assert!(list[1].last_transition_time.is_some());
+    }
+
+    #[test]
+    fn ingress_backend_references_a_named_service_port() {
+        let with_backend = |port: &str| {
+            spec(json!({
+                "ingress_host": "web.example.com",
+                "extra_ports": [{ "name": "metrics", "container_port": 9090 }],
+                "ingress_backend_port": port,
+            }))
+        };
+        let backend_port = |spec: &RustOperatorSpec| {
+            let rules = desired(spec).ingress.unwrap().spec.unwrap().rules.unwrap();
+            let path = &rules[0].http.as_ref().unwrap().paths[0];
+            path.backend.service.as_ref().unwrap().port.clone().unwrap()
+        };
+
+        let named = with_backend("metrics");
+        assert_eq!(backend_port(&named).name.as_deref(), Some("metrics"));
+        let service_ports = service_spec(&named).ports.unwrap();
+        assert!(
+            service_ports
+                .iter()
+                .any(|p| p.name.as_deref() == Some("metrics"))
+        );
+
+        assert!(validate_spec(&with_backend("admin")).is_err());
+        assert!(validate_spec(&with_backend("9090")).is_ok());
+
+        let default = spec(json!({ "ingress_host": "web.example.com" }));
+        let primary = service_spec(&default).ports.unwrap()[0].port;
+        assert_eq!(backend_port(&default).number, Some(primary));
+    }
+
+    #[test]
+    fn exceeding_the_page_cap_is_rejected() {
+        let pages = |n: usize| {
+            let files: serde_json::Map<_, _> = (0..n)
+                .map(|i| (format!("page-{i}.html"), json!("<p>hi</p>")))
+                .collect();
+            spec(json!({ "files": files }))
+        };
+        assert!(validate_spec(&pages(MAX_FILES)).is_ok());
+        let err = validate_spec(&pages(MAX_FILES + 1)).unwrap_err();
+        assert_eq!(
+            err,
+            format!(
+                "files has {} entries; at most {MAX_FILES} are allowed",
+                MAX_FILES + 1
+            )
+        );
+        assert!(build_desired_state(&pages(MAX_FILES + 1), "web", None, owner()).is_err());
+    }
+
+    #[test]
+    fn extra_labels_merge_but_core_labels_win() {
+        let mut with_extra = every_child();
+        with_extra.extra_labels = BTreeMap::from([
+            ("team".into(), "web".into()),
+            ("app.kubernetes.io/name".into(), "hijacked".into()),
+        ]);
+        with_extra.extra_annotations = BTreeMap::from([("cost-center".into(), "42".into())]);
+        let state = desired(&with_extra);
+
+        let deploy = state.deployment.as_ref().unwrap();
+        let labels = deploy.metadata.labels.as_ref().unwrap();
+        assert_eq!(labels["team"], "web");
+        assert_eq!(labels["app.kubernetes.io/name"], "webapp");
+        let selector = deploy.spec.as_ref().unwrap().selector.match_labels.as_ref();
+        assert_eq!(selector, Some(&super::labels("web")));
+
+        for meta in child_metas(&state) {
+            assert_eq!(meta.labels.as_ref().unwrap()["team"], "web");
+            assert_eq!(meta.annotations.as_ref().unwrap()["cost-center"], "42");
+        }
+    }
+
+    #[test]
+    fn ingress_path_and_path_type_are_configurable() {
+        let with_path = |path_type: &str| {
+            spec(json!({
+                "ingress_host": "web.example.com",
+                "ingress_path": "/app",
+                "ingress_path_type": path_type,
+            }))
+        };
+        let rules = desired(&with_path("Exact"))
+            .ingress
+            .unwrap()
+            .spec
+            .unwrap()
+            .rules
+            .unwrap();
+        let path = &rules[0].http.as_ref().unwrap().paths[0];
+        assert_eq!(path.path.as_deref(), Some("/app"));
+        assert_eq!(path.path_type, "Exact");
+
+        assert!(validate_spec(&with_path("Regex")).is_err());
+        let defaults = spec(json!({ "ingress_host": "web.example.com" }));
+        let rules = desired(&defaults)
+            .ingress
+            .unwrap()
+            .spec
+            .unwrap()
+            .rules
+            .unwrap();
+        let path = &rules[0].http.as_ref().unwrap().paths[0];
+        assert_eq!(
+            (path.path.as_deref(), path.path_type.as_str()),
+            (Some("/"), "Prefix")
+        );
+    }
+
+    #[test]
+    fn empty_ingress_path_and_path_type_fall_back_to_defaults() {
+        let empty = spec(json!({ "ingress_path": "", "ingress_path_type": " " }));
+        assert!(validate_spec(&empty).is_ok());
+
+        let empty = spec(json!({
+            "ingress_host": "web.example.com",
+            "ingress_path": "",
+            "ingress_path_type": "",
+        }));
+        assert!(validate_spec(&empty).is_ok());
+        let rules = desired(&empty)
+            .ingress
+            .unwrap()
+            .spec
+            .unwrap()
+            .rules
+            .unwrap();
+        let path = &rules[0].http.as_ref().unwrap().paths[0];
+        assert_eq!(
+            (path.path.as_deref(), path.path_type.as_str()),
+            (Some("/"), "Prefix")
+        );
+    }
+
+    #[test]
+    fn ingress_class_name_reaches_the_ingress_spec() {
+        let class_of = |class: serde_json::Value| {
+            let spec =
+                spec(json!({ "ingress_host": "web.example.com", "ingress_class_name": class }));
+            desired(&spec)
+                .ingress
+                .unwrap()
+                .spec
+                .unwrap()
+                .ingress_class_name
+        };
+        assert_eq!(class_of(json!("traefik")).as_deref(), Some("traefik"));
+        assert_eq!(class_of(json!("")), None);
+        assert_eq!(class_of(serde_json::Value::Null), None);
+    }
+
+    #[test]
+    fn ingress_annotations_land_on_the_ingress() {
+        let with_annotations = spec(json!({
+            "ingress_host": "web.example.com",
+            "ingress_annotations": {
+                "nginx.ingress.kubernetes.io/ssl-redirect": "true",
+                OWNER_UID_ANNOTATION: "spoofed",
+            },
+        }));
+        let ingress = desired(&with_annotations).ingress.unwrap();
+        let annotations = ingress.metadata.annotations.unwrap();
+        assert_eq!(
+            annotations["nginx.ingress.kubernetes.io/ssl-redirect"],
+            "true"
+        );
+        assert_eq!(annotations[OWNER_UID_ANNOTATION], owner().uid);
+
+        let plain = desired(&spec(json!({ "ingress_host": "web.example.com" })));
+        let annotations = plain.ingress.unwrap().metadata.annotations.unwrap();
+        assert_eq!(
+            annotations.keys().collect::<Vec<_>>(),
+            [OWNER_UID_ANNOTATION]
+        );
+    }
+
+    #[test]
+    fn sync_wave_annotation_lands_on_the_deployment() {
+        const SYNC_WAVE: &str = "argocd.argoproj.io/sync-wave";
+        let with_waves = spec(json!({
+            "child_annotations": {
+                "Deployment": { SYNC_WAVE: "1" },
+                "ConfigMap": { SYNC_WAVE: "0" },
+            },
+            "extra_annotations": { SYNC_WAVE: "5", "team": "web" },
+        }));
+        let state = desired(&with_waves);
+        let deploy = state.deployment.unwrap().metadata.annotations.unwrap();
+        assert_eq!(deploy[SYNC_WAVE], "1");
+        assert_eq!(deploy["team"], "web");
+        assert!(deploy.contains_key(OWNER_UID_ANNOTATION));
+        assert_eq!(
+            state.configmap.metadata.annotations.unwrap()[SYNC_WAVE],
+            "0"
+        );
+        assert_eq!(
+            state.service.unwrap().metadata.annotations.unwrap()[SYNC_WAVE],
+            "5"
+        );
+
+        let unknown = spec(json!({ "child_annotations": { "Secret": { SYNC_WAVE: "1" } } }));
+        assert!(validate_spec(&unknown).is_err());
+    }
+
+    #[test]
+    fn ttl_counts_from_the_ready_transition() {
+        use crate::crd::HwCondition;
+
+        let ready = |status: &str| {
+            vec![HwCondition {
+                type_: "Ready".into(),
+                status: status.into(),
+                last_transition_time: Some("2020-01-01T00:00:00Z".into()),
+                ..Default::default()
+            }]
+        };
+        let with_ttl = spec(json!({ "ttl_seconds_after_ready": 90 }));
+        let at = ttl_expiry(&with_ttl, &ready("True")).unwrap();
+        assert_eq!(at.to_rfc3339(), "2020-01-01T00:01:30+00:00");
+        assert_eq!(ttl_expiry(&with_ttl, &ready("False")), None);
+        assert_eq!(ttl_expiry(&spec(json!({})), &ready("True")), None);
+    }
+
+    #[test]
+    fn revision_annotation_follows_the_generation() {
+        let annotations = |spec: &RustOperatorSpec, generation| {
+            let state = build_desired_state(spec, "web", Some(generation), owner()).unwrap();
+            state.deployment.unwrap().metadata.annotations.unwrap()
+        };
+        let v1 = spec(json!({ "annotate_revision": true, "html": "<p>v1</p>" }));
+        let v2 = spec(json!({ "annotate_revision": true, "html": "<p>v2</p>" }));
+
+        let first = annotations(&v1, 3);
+        assert_eq!(first[REVISION_ANNOTATION], "3");
+        assert_eq!(first[ROLLOUT_HASH_ANNOTATION], fingerprint(&v1));
+        let second = annotations(&v2, 4);
+        assert_eq!(second[REVISION_ANNOTATION], "4");
+        assert_eq!(second[ROLLOUT_HASH_ANNOTATION], fingerprint(&v2));
+        assert_eq!(annotations(&v2, 4), second);
+
+        let plain = annotations(&spec(json!({})), 4);
+        assert!(!plain.contains_key(REVISION_ANNOTATION));
+        assert!(!plain.contains_key(ROLLOUT_HASH_ANNOTATION));
+    }
+
+    #[test]
+    fn sanitized_message_keeps_text_and_caps_length() {
+        assert_eq!(sanitize_message("héllo wörld ✓"), "héllo wörld ✓");
+        assert_eq!(sanitize_message("a\tb"), r"a\tb");
+        let long = sanitize_message(&"x".repeat(MAX_MESSAGE_CHARS + 10));
+        assert_eq!(long.chars().count(), MAX_MESSAGE_CHARS + 1);
+        assert!(long.ends_with('…'));
+    }
+
+    #[test]
+    fn designated_primary_port_drives_the_backend_and_url() {
+        let ports = json!([{ "name": "alt", "container_port": 8081, "service_port": 9000 }]);
+        let with_primary = |fields: serde_json::Value| {
+            let mut all = json!({ "extra_ports": ports, "primary_port": "alt" });
+            all.as_object_mut()
+                .unwrap()
+                .extend(fields.as_object().unwrap().clone());
+            spec(all)
+        };
+
+        let internal = with_primary(json!({}));
+        assert_eq!(
+            site_url(&internal, "web", "shop").as_deref(),
+            Some("http://web-service.shop.svc:9000")
+        );
+        let exposed = with_primary(json!({ "ingress_host": "web.example.com" }));
+        let rules = desired(&exposed)
+            .ingress
+            .unwrap()
+            .spec
+            .unwrap()
+            .rules
+            .unwrap();
+        let backend = rules[0].http.as_ref().unwrap().paths[0]
+            .backend
+            .service
+            .clone();
+        assert_eq!(backend.unwrap().port.unwrap().number, Some(9000));
+        assert_eq!(
+            site_url(&exposed, "web", "shop").as_deref(),
+            Some("http://web.example.com/")
+        );
+
+        assert!(validate_spec(&with_primary(json!({ "primary_port": "missing" }))).is_err());
+    }
+
+    #[test]
+    fn scheduling_fields_reach_the_pod_spec() {
+        let pinned = pod_spec(&spec(json!({
+            "node_selector": { "pool": "web" },
+            "tolerations": [{ "key": "dedicated", "operator": "Equal", "value": "web", "effect": "NoSchedule" }],
+            "affinity": { "nodeAffinity": { "preferredDuringSchedulingIgnoredDuringExecution": [
+                { "weight": 1, "preference": { "matchExpressions": [
+                    { "key": "zone", "operator": "In", "values": ["a"] },
+                ] } },
+            ] } },
+        })));
+        assert_eq!(
+            pinned.node_selector,
+            Some(BTreeMap::from([("pool".into(), "web".into())]))
+        );
+        let tolerations = pinned.tolerations.unwrap();
+        assert_eq!(tolerations[0].key.as_deref(), Some("dedicated"));
+        assert_eq!(tolerations[0].effect.as_deref(), Some("NoSchedule"));
+        assert!(pinned.affinity.unwrap().node_affinity.is_some());
+
+        let plain = pod_spec(&spec(json!({})));
+        assert_eq!(plain.node_selector, None);
+        assert_eq!(plain.tolerations, None);
+        assert_eq!(plain.affinity, None);
+    }
+
+    #[test]
+    fn deployment_strategy_follows_the_spec() {
+        let strategy = |s: serde_json::Value| {
+            desired(&spec(json!({ "strategy": s })))
+                .deployment
+                .unwrap()
+                .spec
+                .unwrap()
+                .strategy
+        };
+        let recreate = strategy(json!({ "type": "Recreate" })).unwrap();
+        assert_eq!(recreate.type_.as_deref(), Some("Recreate"));
+        assert_eq!(recreate.rolling_update, None);
+
+        let rolling = json!({ "type": "RollingUpdate", "max_surge": "1", "max_unavailable": "0%" });
+        let rolling = strategy(rolling).unwrap().rolling_update.unwrap();
+        assert_eq!(rolling.max_surge, Some(IntOrString::Int(1)));
+        assert_eq!(
+            rolling.max_unavailable,
+            Some(IntOrString::String("0%".into()))
+        );
+
+        assert_eq!(strategy(serde_json::Value::Null), None);
+        for bad in [
+            json!({ "type": "BlueGreen" }),
+            json!({ "type": "Recreate", "max_surge": "1" }),
+            json!({ "type": "RollingUpdate", "max_surge": "lots" }),
+        ] {
+            assert!(validate_spec(&spec(json!({ "strategy": bad }))).is_err());
+        }
+    }
+
+    #[test]
+    fn desired_pdb_selects_the_pods() {
+        let pdb = |min: &str| {
+            let state = desired(&spec(json!({ "pdb_min_available": min })));
+            state.pdb.unwrap()
+        };
+        let counted = pdb("2");
+        assert_eq!(counted.metadata.name.as_deref(), Some("web"));
+        assert_eq!(counted.metadata.owner_references, Some(vec![owner()]));
+        let pdb_spec = counted.spec.unwrap();
+        assert_eq!(pdb_spec.min_available, Some(IntOrString::Int(2)));
+        assert_eq!(
+            pdb_spec.selector.unwrap().match_labels,
+            Some(super::labels("web"))
+        );
+        let percent = pdb("50%").spec.unwrap().min_available;
+        assert_eq!(percent, Some(IntOrString::String("50%".into())));
+
+        assert!(desired(&spec(json!({}))).pdb.is_none());
+        assert!(validate_spec(&spec(json!({ "pdb_min_available": "half" }))).is_err());
+    }
+
+    #[test]
+    fn service_account_is_created_or_only_referenced() {
+        let created = desired(&spec(json!({
+            "service_account": "web-sa",
+            "create_service_account": true,
+        })));
+        let sa = created.service_account.as_ref().unwrap();
+        assert_eq!(sa.metadata.name.as_deref(), Some("web-sa"));
+        assert_eq!(sa.metadata.owner_references, Some(vec![owner()]));
+        let pod = created
+            .deployment
+            .unwrap()
+            .spec
+            .unwrap()
+            .template
+            .spec
+            .unwrap();
+        assert_eq!(pod.service_account_name.as_deref(), Some("web-sa"));
+
+        let referenced = spec(json!({ "service_account": "workload-identity" }));
+        assert!(desired(&referenced).service_account.is_none());
+        let pod = pod_spec(&referenced);
+        assert_eq!(
+            pod.service_account_name.as_deref(),
+            Some("workload-identity")
+        );
+
+        assert_eq!(pod_spec(&spec(json!({}))).service_account_name, None);
+        assert!(validate_spec(&spec(json!({ "create_service_account": true }))).is_err());
+    }
+
+    #[test]
+    fn two_hosts_give_two_rules_and_tls_hosts() {
+        let state = desired(&spec(json!({
+            "ingress_hosts": ["a.example.com", "b.example.com"],
+            "tls_secret_name": "web-tls",
+        })));
+        let ingress = state.ingress.unwrap().spec.unwrap();
+        let hosts: Vec<_> = ingress
+            .rules
+            .unwrap()
+            .into_iter()
+            .map(|r| r.host.unwrap())
+            .collect();
+        assert_eq!(hosts, ["a.example.com", "b.example.com"]);
+        let tls = ingress.tls.unwrap();
+        assert_eq!(tls.len(), 1);
+        assert_eq!(tls[0].secret_name.as_deref(), Some("web-tls"));
+        assert_eq!(tls[0].hosts.as_deref(), Some(&hosts[..]));
+
+        // The single-host field still works and joins the list.
+        let both =
+            spec(json!({ "ingress_host": "a.example.com", "ingress_hosts": ["b.example.com"] }));
+        let rules = desired(&both).ingress.unwrap().spec.unwrap().rules.unwrap();
+        assert_eq!(rules.len(), 2);
+    }
+
+    #[test]
+    fn command_and_args_override_the_entrypoint() {
+        let custom = spec(json!({
+            "command": ["/bin/sh", "-c"],
+            "args": ["exec nginx -g 'daemon off;'"],
+        }));
+        let container = pod_spec(&custom).containers.remove(0);
+        assert_eq!(container.command.unwrap(), ["/bin/sh", "-c"]);
+        assert_eq!(container.args.unwrap(), ["exec nginx -g 'daemon off;'"]);
+
+        let default = spec(json!({}));
+        let container = pod_spec(&default).containers.remove(0);
+        assert_eq!((container.command, container.args), (None, None));
+        assert_ne!(fingerprint(&default), fingerprint(&custom));
+    }
+
+    #[test]
+    fn network_policy_admits_only_the_container_port() {
+        let hardened = spec(json!({ "network_policy": true, "container_port": 8080 }));
+        let state = desired(&hardened);
+        let pod_labels = state
+            .deployment
+            .unwrap()
+            .spec
+            .unwrap()
+            .template
+            .metadata
+            .unwrap()
+            .labels
+            .unwrap();
+        let policy = state.network_policy.unwrap().spec.unwrap();
+
+        let selected = policy.pod_selector.unwrap().match_labels.unwrap();
+        assert!(selected.iter().all(|(k, v)| pod_labels.get(k) == Some(v)));
+        assert_eq!(policy.policy_types.unwrap(), ["Ingress"]);
+        let rules = policy.ingress.unwrap();
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0].from, None);
+        let ports = rules[0].ports.clone().unwrap();
+        assert_eq!(ports.len(), 1);
+        assert_eq!(ports[0].port, Some(IntOrString::Int(8080)));
+        assert_eq!(ports[0].protocol.as_deref(), Some("TCP"));
+
+        assert!(desired(&spec(json!({}))).network_policy.is_none());
+    }
+
+    #[test]
+    fn service_selector_mismatch_is_detected() {
+        let state = desired(&spec(json!({})));
+        let svc = state.service.unwrap();
+        let mut deploy = state.deployment.unwrap();
+        assert!(service_selects_pods(&svc, &deploy));
+
+        let template = deploy.spec.as_mut().unwrap().template.metadata.as_mut();
+        template
+            .unwrap()
+            .labels
+            .as_mut()
+            .unwrap()
+            .insert("app.kubernetes.io/instance".into(), "other".into());
+        assert!(!service_selects_pods(&svc, &deploy));
+    }
+
+    #[test]
+    fn init_containers_fill_a_shared_content_volume() {
+        let spec = spec(json!({
+            "html_mount_path": "/etc/site",
+            "init_content_mount_path": "/usr/share/nginx/html",
+            "init_containers": [{ "name": "fetch", "image": "curl", "command": ["sh", "-c", "true"] }],
+        }));
+        let deploy = desired(&spec).deployment.unwrap();
+        let pod = deploy.spec.unwrap().template.spec.unwrap();
+        let content = pod
+            .volumes
+            .unwrap()
+            .into_iter()
+            .find(|v| v.name == CONTENT_VOLUME)
+            .unwrap();
+        assert!(content.empty_dir.is_some());
+
+        let init = &pod.init_containers.unwrap()[0];
+        assert_eq!(init.name, "fetch");
+        assert_eq!(
+            init.command.as_deref(),
+            Some(&["sh".into(), "-c".into(), "true".into()][..])
+        );
+        let init_mount = init
+            .volume_mounts
+            .iter()
+            .flatten()
+            .find(|m| m.name == CONTENT_VOLUME)
+            .unwrap();
+        assert_eq!(init_mount.mount_path, CONTENT_MOUNT_PATH);
+        assert_ne!(init_mount.read_only, Some(true));
+
+        let served = pod.containers[0].volume_mounts.as_ref().unwrap();
+        let served_mount = served.iter().find(|m| m.name == CONTENT_VOLUME).unwrap();
+        assert_eq!(served_mount.mount_path, "/usr/share/nginx/html");
+    }
+
+    #[test]
+    fn init_content_mount_path_default_or_unused_keeps_fingerprint() {
+        let init = json!([{ "name": "fetch", "image": "curl" }]);
+        let unset = fingerprint(&spec(json!({ "init_containers": init })));
+        let explicit = spec(json!({
+            "init_containers": init,
+            "init_content_mount_path": CONTENT_MOUNT_PATH,
+        }));
+        assert_eq!(unset, fingerprint(&explicit));
+        let custom = spec(json!({ "init_containers": init, "init_content_mount_path": "/srv" }));
+        assert_ne!(unset, fingerprint(&custom));
+
+        let without_init = spec(json!({ "init_content_mount_path": "/srv" }));
+        assert_eq!(fingerprint(&spec(json!({}))), fingerprint(&without_init));
+    }
+
+    #[test]
+    fn no_content_volume_without_init_containers() {
+        let pod = desired(&spec(json!({})))
+            .deployment
+            .unwrap()
+            .spec
+            .unwrap()
+            .template
+            .spec
+            .unwrap();
+        assert!(pod.init_containers.is_none());
+        assert!(
+            pod.volumes
+                .unwrap()
+                .iter()
+                .all(|v| v.name != CONTENT_VOLUME)
+        );
+    }
+
+    #[test]
+    fn html_mount_path_is_used_and_default_keeps_fingerprint() {
+        let custom = spec(json!({ "html_mount_path": "/srv/www" }));
+        let deploy = desired(&custom).deployment.unwrap();
+        let pod = deploy.spec.unwrap().template.spec.unwrap();
+        let mounts = pod.containers[0].volume_mounts.as_ref().unwrap();
+        let html = mounts.iter().find(|m| m.name == HTML_VOLUME).unwrap();
+        assert_eq!(html.mount_path, "/srv/www");
+
+        let unset = fingerprint(&spec(json!({})));
+        let explicit = fingerprint(&spec(json!({ "html_mount_path": DEFAULT_HTML_MOUNT_PATH })));
+        assert_eq!(unset, explicit);
+        assert_ne!(unset, fingerprint(&custom));
+    }
+
+    #[test]
+    fn html_read_only_false_makes_the_mount_writable() {
+        let writable = spec(json!({ "html_read_only": false }));
+        let deploy = desired(&writable).deployment.unwrap();
+        let pod = deploy.spec.unwrap().template.spec.unwrap();
+        let mounts = pod.containers[0].volume_mounts.as_ref().unwrap();
+        let html = mounts.iter().find(|m| m.name == HTML_VOLUME).unwrap();
+        assert_eq!(html.read_only, Some(false));
+
+        let unset = fingerprint(&spec(json!({})));
+        assert_eq!(unset, fingerprint(&spec(json!({ "html_read_only": true }))));
+        assert_ne!(unset, fingerprint(&writable));
+    }
+
+    #[test]
+    fn dot_keys_are_rejected() {
+        for key in [".", ".."] {
+            assert!(validate_spec(&spec(json!({ "configmap_key": key }))).is_err());
+            assert!(validate_spec(&spec(json!({ "files": { key: "x" } }))).is_err());
+        }
+        assert!(validate_spec(&spec(json!({ "files": { ".well-known": "x" } }))).is_ok());
+        assert!(validate_spec(&spec(json!({ "configmap_key": "page.html" }))).is_ok());
+    }
+
+    #[test]
+    fn content_and_html_mounts_must_differ() {
+        let spec = spec(json!({
+            "init_containers": [{ "name": "fetch", "image": "curl" }],
+            "init_content_mount_path": "/usr/share/nginx/html",
+        }));
+        assert!(validate_spec(&spec).is_err());
     }
 }
@@ -0,0 +1,32 @@
+use opentelemetry::trace::TracerProvider;
+use opentelemetry_otlp::SpanExporter;
+use opentelemetry_sdk::{Resource, trace::SdkTracerProvider};
+use tracing::Subscriber;
+use tracing_subscriber::{Layer, registry::LookupSpan};
+
+/// OTLP/gRPC trace exporter, only built when `OTEL_EXPORTER_OTLP_ENDPOINT` is set.
+/// The exporter reads the endpoint (and the other standard `OTEL_*` variables) itself.
+pub fn otlp_provider() -> anyhow::Result<Option<SdkTracerProvider>> {
+    if std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT").is_ok_and(|e| !e.trim().is_empty()) {
+        let exporter = SpanExporter::builder().with_tonic().build()?;
+        let provider = SdkTracerProvider::builder()
+            .with_batch_exporter(exporter)
+            .with_resource(
+                Resource::builder()
+                    .with_service_name("rust-operator")
+                    .build(),
+            )
+            .build();
+        Ok(Some(provider))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Bridges `tracing` spans (e.g. `reconcile` and `apply_child`) into `provider`.
+pub fn layer<S>(provider: &SdkTracerProvider) -> impl Layer<S>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    tracing_opentelemetry::layer().with_tracer(provider.tracer("rust-operator"))
+}
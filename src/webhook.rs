@@ -0,0 +1,150 @@
+use std::net::SocketAddr;
+
+use axum::{Json, Router, routing::post};
+use axum_server::tls_rustls::RustlsConfig;
+use kube::core::{
+    DynamicObject,
+    admission::{AdmissionRequest, AdmissionResponse, AdmissionReview},
+};
+use tracing::info;
+
+use crate::{crd::RustOperator, resources::validate_spec};
+
+/// Bind address for the validating webhook, from `WEBHOOK_ADDR` (unset or empty disables it).
+pub fn webhook_addr() -> anyhow::Result<Option<SocketAddr>> {
+    match std::env::var("WEBHOOK_ADDR") {
+        Ok(addr) if !addr.trim().is_empty() => Ok(Some(addr.parse()?)),
+        _ => Ok(None),
+    }
+}
+
+/// Directory holding `tls.crt` and `tls.key`, from `WEBHOOK_CERT_DIR`.
+fn cert_dir() -> String {
+    std::env::var("WEBHOOK_CERT_DIR")
+        .unwrap_or_else(|_| "/tmp/k8s-webhook-server/serving-certs".into())
+}
+
+/// Answers an AdmissionReview: allowed unless the submitted RustOperator fails `validate_spec`.
+pub fn review(body: serde_json::Value) -> AdmissionReview<DynamicObject> {
+    let req: AdmissionRequest<RustOperator> =
+        match serde_json::from_value::<AdmissionReview<RustOperator>>(body)
+            .map_err(|e| e.to_string())
+            .and_then(|r| r.try_into().map_err(|e| format!("{e}")))
+        {
+            Ok(req) => req,
+            Err(msg) => return AdmissionResponse::invalid(msg).into_review(),
+        };
+    let resp = AdmissionResponse::from(&req);
+    // DELETE carries no object, and there is nothing to check then.
+    match req.object.as_ref().map(|obj| validate_spec(&obj.spec)) {
+        Some(Err(msg)) => {
+            info!("denying {:?} of {}: {msg}", req.operation, req.name);
+            resp.deny(msg).into_review()
+        }
+        _ => resp.into_review(),
+    }
+}
+
+async fn validate_handler(
+    Json(body): Json<serde_json::Value>,
+) -> Json<AdmissionReview<DynamicObject>> {
+    Json(review(body))
+}
+
+/// Serves `/validate` over HTTPS until the task is aborted.
+pub async fn serve(addr: SocketAddr) -> anyhow::Result<()> {
+    let dir = cert_dir();
+    let tls =
+        RustlsConfig::from_pem_file(format!("{dir}/tls.crt"), format!("{dir}/tls.key")).await?;
+    let app = Router::new().route("/validate", post(validate_handler));
+    info!("serving validating webhook on {addr}");
+    axum_server::bind_rustls(addr, tls)
+        .serve(app.into_make_service())
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::*;
+
+    /// An AdmissionReview as the API server sends it for `operation` on a `web` RustOperator.
+    fn admission_review(operation: &str, spec: Option<Value>) -> Value {
+        let object = spec.map(|spec| {
+            json!({
+                "apiVersion": "rootster.xyz/v1",
+                "kind": "RustOperator",
+                "metadata": { "name": "web", "namespace": "default" },
+                "spec": spec,
+            })
+        });
+        json!({
+            "apiVersion": "admission.k8s.io/v1",
+            "kind": "AdmissionReview",
+            "request": {
+                "uid": "req-1",
+                "kind": { "group": "rootster.xyz", "version": "v1", "kind": "RustOperator" },
+                "resource": { "group": "rootster.xyz", "version": "v1", "resource": "rustoperators" },
+                "name": "web",
+                "namespace": "default",
+                "operation": operation,
+                "userInfo": { "username": "admin" },
+                "object": object,
+            },
+        })
+    }
+
+    fn response(body: Value) -> AdmissionResponse {
+        review(body).response.unwrap()
+    }
+
+    #[test]
+    fn valid_spec_is_allowed() {
+        let resp = response(admission_review(
+            "CREATE",
+            Some(json!({ "message": "hello" })),
+        ));
+        assert!(resp.allowed);
+        assert_eq!(resp.uid, "req-1");
+    }
+
+    #[test]
+    fn invalid_specs_are_denied_with_the_reason() {
+        for (spec, reason) in [
+            (
+                json!({ "message": "hi", "replicas": -1 }),
+                "replicas must not be negative",
+            ),
+            (
+                json!({ "message": "hi", "ingress_host": "not a host" }),
+                "is not a valid DNS name",
+            ),
+            (
+                json!({ "message": "hi", "service_type": "ExternalName" }),
+                "service_type",
+            ),
+        ] {
+            let resp = response(admission_review("UPDATE", Some(spec)));
+            assert!(!resp.allowed);
+            assert_eq!(resp.uid, "req-1");
+            assert!(
+                resp.result.message.contains(reason),
+                "{}",
+                resp.result.message
+            );
+        }
+    }
+
+    #[test]
+    fn delete_without_an_object_is_allowed() {
+        assert!(response(admission_review("DELETE", None)).allowed);
+    }
+
+    #[test]
+    fn malformed_review_is_rejected() {
+        let resp = response(json!({ "kind": "AdmissionReview", "request": { "uid": 7 } }));
+        assert!(!resp.allowed);
+    }
+}